@@ -0,0 +1,181 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+
+use minitri::random::TritRng;
+use minitri::{Encoding, TritVec, T1B1, T3B1, T5B1};
+
+/// Number of trits per input: a transaction hash, a transaction and a large blob.
+///
+/// NOTE: all conversions currently require a multiple of 15 trits, so the sizes are rounded up
+/// to the next such multiple.
+const SIZES: [usize; 3] = [243, 8019, 1_000_000];
+
+fn round_up(n: usize) -> usize {
+    n.div_ceil(15) * 15
+}
+
+fn trits(n: usize) -> T1B1 {
+    TritRng::seeded(0x6d69_6e69_7472_6921).t1b1(n)
+}
+
+fn t1b1_t3b1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("T1B1 <-> T3B1");
+
+    for size in SIZES.iter().map(|n| round_up(*n)) {
+        let t1b1 = trits(size);
+        let t3b1: T3B1 = t1b1.clone().into();
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("T1B1 -> T3B1", size), &t1b1, |b, t1b1| {
+            b.iter_batched(|| t1b1.clone(), T3B1::from, BatchSize::LargeInput)
+        });
+        group.bench_with_input(BenchmarkId::new("T3B1 -> T1B1", size), &t3b1, |b, t3b1| {
+            b.iter_batched(|| t3b1.clone(), T1B1::from, BatchSize::LargeInput)
+        });
+    }
+
+    group.finish();
+}
+
+fn t1b1_t5b1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("T1B1 <-> T5B1");
+
+    for size in SIZES.iter().map(|n| round_up(*n)) {
+        let t1b1 = trits(size);
+        let t5b1: T5B1 = t1b1.clone().into();
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("T1B1 -> T5B1", size), &t1b1, |b, t1b1| {
+            b.iter_batched(|| t1b1.clone(), T5B1::from, BatchSize::LargeInput)
+        });
+        group.bench_with_input(BenchmarkId::new("T5B1 -> T1B1", size), &t5b1, |b, t5b1| {
+            b.iter_batched(|| t5b1.clone(), T1B1::from, BatchSize::LargeInput)
+        });
+    }
+
+    group.finish();
+}
+
+/// The decoder before T5B1 was decoded straight into `BalancedTrit` storage: every byte is
+/// looked up in a table of `i8` trits, which are copied into a buffer, and `T1B1::from_i8`
+/// copies that buffer again.
+fn decode_double_copy(t5b1: T5B1, lut: &[[i8; 5]]) -> T1B1 {
+    let mut trits = vec![0i8; t5b1.len() * 5];
+
+    for (i, byte) in t5b1.as_bytes().iter().enumerate() {
+        trits[i * 5..(i + 1) * 5].copy_from_slice(&lut[*byte as usize]);
+    }
+
+    T1B1::from_i8(&trits)
+}
+
+/// The `i8` table of `decode_double_copy`, taken from the current decoder.
+fn double_copy_lut() -> Vec<[i8; 5]> {
+    (0..243u8)
+        .map(|byte| {
+            let trits: T1B1 = T5B1::from_u8(&[byte]).into();
+            let mut group = [0i8; 5];
+            for (t, trit) in group.iter_mut().zip(trits.trits()) {
+                *t = trit as i8;
+            }
+            group
+        })
+        .collect()
+}
+
+/// `T5B1 -> T1B1` with the decoder picked at compile time, next to the old double copy as the
+/// baseline. Run the group once without and once with `--features t5b1-arith-decode`; the
+/// benchmark id names the decoder, so all three show up side by side in the report.
+fn t5b1_decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("T5B1 decode");
+    let decoder = if cfg!(feature = "t5b1-arith-decode") {
+        "arith"
+    } else {
+        "LUT"
+    };
+    let lut = double_copy_lut();
+
+    for size in SIZES.iter().map(|n| round_up(*n)) {
+        let t5b1: T5B1 = trits(size).into();
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new(decoder, size), &t5b1, |b, t5b1| {
+            b.iter_batched(|| t5b1.clone(), T1B1::from, BatchSize::LargeInput)
+        });
+        group.bench_with_input(BenchmarkId::new("double copy", size), &t5b1, |b, t5b1| {
+            b.iter_batched(
+                || t5b1.clone(),
+                |t5b1| decode_double_copy(t5b1, &lut),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+fn t3b1_t5b1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("T3B1 -> T5B1");
+
+    for size in SIZES.iter().map(|n| round_up(*n)) {
+        let t3b1: T3B1 = trits(size).into();
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &t3b1, |b, t3b1| {
+            b.iter_batched(|| t3b1.clone(), T5B1::from, BatchSize::LargeInput)
+        });
+    }
+
+    group.finish();
+}
+
+fn tritvec_push(c: &mut Criterion) {
+    let mut group = c.benchmark_group("TritVec::push");
+
+    for size in SIZES.iter().map(|n| round_up(*n)) {
+        let t1b1 = trits(size);
+
+        group.throughput(Throughput::Elements(size as u64));
+        group.bench_with_input(BenchmarkId::new("T1B1", size), &t1b1, |b, t1b1| {
+            b.iter_batched(
+                || (TritVec::<T1B1>::with_capacity(size), t1b1.clone()),
+                |(mut vec, trits)| {
+                    vec.push(trits);
+                    vec
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("T3B1", size), &t1b1, |b, t1b1| {
+            b.iter_batched(
+                || (TritVec::<T3B1>::with_capacity(size / 3), t1b1.clone()),
+                |(mut vec, trits)| {
+                    vec.push(trits);
+                    vec
+                },
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("T5B1", size), &t1b1, |b, t1b1| {
+            b.iter_batched(
+                || (TritVec::<T5B1>::with_capacity(size / 5), t1b1.clone()),
+                |(mut vec, trits)| {
+                    vec.push(trits);
+                    vec
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    t1b1_t3b1,
+    t1b1_t5b1,
+    t5b1_decode,
+    t3b1_t5b1,
+    tritvec_push
+);
+criterion_main!(benches);
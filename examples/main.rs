@@ -1,5 +1,6 @@
-use minitri::{BalancedTrit, BalancedTryte, Encoding, TritVec, T1B1, T3B1, T5B1};
+use minitri::{BalancedTrit, BalancedTryte, TritVec, T1B1, T3B1, T5B1};
 
+#[allow(unused_variables)]
 fn main() {
     // Create single trits and trytes from their char repr.
     let trit: BalancedTrit = '-'.into();
@@ -0,0 +1,200 @@
+//! Characters used to read and write trits and trytes, for interoperating with tools that don't
+//! use `-01` and `9A..ZN..`.
+
+use crate::encodings::iter::{Trits, Trytes};
+use crate::trit::BalancedTrit;
+use crate::tryte::BalancedTryte;
+
+use std::fmt;
+
+/// The characters for -1, 0 and 1.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TritAlphabet {
+    chars: [char; 3],
+}
+
+impl TritAlphabet {
+    /// `-`, `0`, `1`, as used by `Display` and `From<&str>`.
+    pub const DEFAULT: Self = Self::new('-', '0', '1');
+    /// `-`, `0`, `+`
+    pub const SIGNS: Self = Self::new('-', '0', '+');
+    /// `T`, `0`, `1`
+    pub const T01: Self = Self::new('T', '0', '1');
+    /// `N`, `0`, `P`
+    pub const N0P: Self = Self::new('N', '0', 'P');
+    /// `↓`, `0`, `↑`
+    pub const ARROWS: Self = Self::new('↓', '0', '↑');
+
+    /// NOTE: the characters should be distinct, otherwise parsing picks the lowest trit.
+    pub const fn new(minus_one: char, zero: char, plus_one: char) -> Self {
+        Self {
+            chars: [minus_one, zero, plus_one],
+        }
+    }
+
+    pub fn to_char(&self, trit: BalancedTrit) -> char {
+        self.chars[(trit as i8 + 1) as usize]
+    }
+
+    pub fn from_char(&self, c: char) -> Option<BalancedTrit> {
+        self.chars
+            .iter()
+            .position(|a| *a == c)
+            .map(|i| (i as i8 - 1).into())
+    }
+}
+
+impl Default for TritAlphabet {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The characters for the 27 trytes, from -13 to 13.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TryteAlphabet {
+    chars: [char; 27],
+}
+
+impl TryteAlphabet {
+    /// `NOPQRSTUVWXYZ9ABCDEFGHIJKLM`, as used by `Display` and `From<&str>`.
+    pub const DEFAULT: Self = Self::new([
+        'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '9', 'A', 'B', 'C', 'D',
+        'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+    ]);
+    /// Like `DEFAULT`, but in lower case.
+    pub const LOWER: Self = Self::new([
+        'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '9', 'a', 'b', 'c', 'd',
+        'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm',
+    ]);
+    /// Like `DEFAULT`, but with `0` for the zero tryte.
+    pub const ZERO: Self = Self::new([
+        'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '0', 'A', 'B', 'C', 'D',
+        'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M',
+    ]);
+
+    /// NOTE: the characters should be distinct, otherwise parsing picks the lowest tryte.
+    pub const fn new(chars: [char; 27]) -> Self {
+        Self { chars }
+    }
+
+    pub fn to_char(&self, tryte: BalancedTryte) -> char {
+        self.chars[(tryte as i8 + 13) as usize]
+    }
+
+    pub fn from_char(&self, c: char) -> Option<BalancedTryte> {
+        self.chars
+            .iter()
+            .position(|a| *a == c)
+            .map(|i| (i as i8 - 13).into())
+    }
+}
+
+impl Default for TryteAlphabet {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Prints trits with an alphabet, see `T1B1::display_with`.
+#[derive(Clone, Debug)]
+pub struct DisplayTrits<'a> {
+    trits: Trits<'a>,
+    alphabet: TritAlphabet,
+}
+
+impl<'a> DisplayTrits<'a> {
+    pub(crate) fn new(trits: Trits<'a>, alphabet: &TritAlphabet) -> Self {
+        Self {
+            trits,
+            alphabet: *alphabet,
+        }
+    }
+}
+
+impl fmt::Display for DisplayTrits<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for trit in self.trits.clone() {
+            write!(f, "{}", self.alphabet.to_char(trit))?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints trytes with an alphabet, see `T3B1::display_with`.
+#[derive(Clone, Debug)]
+pub struct DisplayTrytes<'a> {
+    trytes: Trytes<'a>,
+    alphabet: TryteAlphabet,
+}
+
+impl<'a> DisplayTrytes<'a> {
+    pub(crate) fn new(trytes: Trytes<'a>, alphabet: &TryteAlphabet) -> Self {
+        Self {
+            trytes,
+            alphabet: *alphabet,
+        }
+    }
+}
+
+impl fmt::Display for DisplayTrytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for tryte in self.trytes.clone() {
+            write!(f, "{}", self.alphabet.to_char(tryte))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_alphabets_match_display() {
+        for trit in [-1, 0, 1] {
+            let trit = BalancedTrit::from(trit);
+            assert_eq!(
+                trit.to_string(),
+                TritAlphabet::DEFAULT.to_char(trit).to_string()
+            );
+        }
+        for tryte in BalancedTryte::ALL.iter() {
+            assert_eq!(tryte.to_char(), TryteAlphabet::DEFAULT.to_char(*tryte));
+        }
+    }
+
+    #[test]
+    fn presets_round_trip() {
+        let trits = [
+            TritAlphabet::DEFAULT,
+            TritAlphabet::SIGNS,
+            TritAlphabet::T01,
+            TritAlphabet::N0P,
+            TritAlphabet::ARROWS,
+        ];
+        for alphabet in trits.iter() {
+            for trit in [-1, 0, 1] {
+                let trit = BalancedTrit::from(trit);
+                assert_eq!(Some(trit), alphabet.from_char(alphabet.to_char(trit)));
+            }
+        }
+
+        let trytes = [
+            TryteAlphabet::DEFAULT,
+            TryteAlphabet::LOWER,
+            TryteAlphabet::ZERO,
+        ];
+        for alphabet in trytes.iter() {
+            for tryte in BalancedTryte::ALL.iter() {
+                assert_eq!(Some(*tryte), alphabet.from_char(alphabet.to_char(*tryte)));
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_characters() {
+        assert_eq!(None, TritAlphabet::T01.from_char('-'));
+        assert_eq!(None, TryteAlphabet::LOWER.from_char('A'));
+    }
+}
@@ -0,0 +1,334 @@
+//! Codecs between arbitrary binary data and trits.
+//!
+//! * b1t6: every byte, read as `i8`, becomes 6 balanced trits (two trytes).
+//! * radix: the bytes are read as one big-endian unsigned integer, which is converted into its
+//!   minimal balanced ternary representation, least significant trit first.
+//!
+//! T5B1 variants pad the trits with zeros up to the next multiple of 5.
+
+use crate::encodings::{t1b1::T1B1, t5b1::T5B1Ref, t5b1::T5B1, Encoding};
+use crate::error::Error;
+use crate::trit::BalancedTrit;
+
+const B1T6_TRITS: usize = 6;
+
+pub fn encode_b1t6(bytes: &[u8]) -> T1B1 {
+    let mut trits = T1B1::with_capacity(bytes.len() * B1T6_TRITS);
+
+    for byte in bytes {
+        let mut value = *byte as i8 as i16;
+        for _ in 0..B1T6_TRITS {
+            let trit = balanced_digit(&mut value);
+            trits.push_internal(trit);
+        }
+    }
+
+    trits
+}
+
+#[allow(clippy::manual_is_multiple_of)]
+pub fn decode_b1t6(trits: &T1B1) -> Result<Vec<u8>, Error> {
+    if trits.len() % B1T6_TRITS != 0 {
+        return Err(Error::InvalidTritLength {
+            len: trits.len(),
+            multiple: B1T6_TRITS,
+        });
+    }
+
+    decode_b1t6_groups(trits.as_slice())
+}
+
+pub fn encode_b1t6_t5b1(bytes: &[u8]) -> T5B1 {
+    pad_to_t5b1(encode_b1t6(bytes))
+}
+
+/// The number of bytes is implied by the number of trits, so at most 4 zero trits of padding
+/// are accepted. Fails for bytes above 242.
+pub fn decode_b1t6_t5b1(bytes: &T5B1) -> Result<Vec<u8>, Error> {
+    let trits: T1B1 = T5B1Ref::new(bytes.as_bytes())?.into();
+    let trits = trits.as_slice();
+    let len = trits.len() / B1T6_TRITS * B1T6_TRITS;
+
+    check_padding(trits, len)?;
+    decode_b1t6_groups(&trits[..len])
+}
+
+fn decode_b1t6_groups(trits: &[BalancedTrit]) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(trits.len() / B1T6_TRITS);
+
+    for (i, group) in trits.chunks(B1T6_TRITS).enumerate() {
+        let value = group
+            .iter()
+            .rev()
+            .fold(0i16, |acc, trit| acc * 3 + *trit as i16);
+
+        if value < i8::MIN as i16 || value > i8::MAX as i16 {
+            return Err(Error::NonCanonicalTrits {
+                offset: i * B1T6_TRITS,
+            });
+        }
+        bytes.push(value as i8 as u8);
+    }
+
+    Ok(bytes)
+}
+
+/// Converts the bytes into the shortest trit sequence with the same numeric value. Leading zero
+/// bytes don't contribute, so the byte length has to be kept separately for decoding.
+pub fn encode_radix(bytes: &[u8]) -> T1B1 {
+    // Little-endian base 256 digits, divided by 3 until nothing is left.
+    let mut number = bytes.iter().rev().copied().collect::<Vec<u8>>();
+    let mut trits = T1B1::with_capacity(bytes.len() * 6);
+
+    while let Some(&0) = number.last() {
+        number.pop();
+    }
+
+    while !number.is_empty() {
+        let mut remainder = 0u16;
+        for digit in number.iter_mut().rev() {
+            let value = remainder * 256 + *digit as u16;
+            *digit = (value / 3) as u8;
+            remainder = value % 3;
+        }
+
+        // A remainder of 2 is -1 with a carry into the quotient.
+        if remainder == 2 {
+            increment(&mut number);
+            trits.push_internal(BalancedTrit::MinusOne);
+        } else {
+            trits.push_internal((remainder as i8).into());
+        }
+
+        while let Some(&0) = number.last() {
+            number.pop();
+        }
+    }
+
+    trits
+}
+
+/// Converts the trits back into `len` big-endian bytes. Fails for negative values, values that
+/// don't fit into `len` bytes, and trit sequences that aren't minimal.
+pub fn decode_radix(trits: &T1B1, len: usize) -> Result<Vec<u8>, Error> {
+    let trits = trits.as_slice();
+
+    if trits.last() == Some(&BalancedTrit::Zero) {
+        return Err(Error::NonCanonicalTrits {
+            offset: trits.len() - 1,
+        });
+    }
+
+    decode_radix_minimal(trits, len)
+}
+
+pub fn encode_radix_t5b1(bytes: &[u8]) -> T5B1 {
+    pad_to_t5b1(encode_radix(bytes))
+}
+
+/// Like `decode_radix`, but the last byte must not be zero, so that at most 4 zero trits of
+/// padding are accepted. Fails for bytes above 242.
+pub fn decode_radix_t5b1(bytes: &T5B1, len: usize) -> Result<Vec<u8>, Error> {
+    let trits: T1B1 = T5B1Ref::new(bytes.as_bytes())?.into();
+    let trits = trits.as_slice();
+    let significant = trits
+        .iter()
+        .rposition(|t| *t != BalancedTrit::Zero)
+        .map_or(0, |i| i + 1);
+
+    if trits.len() - significant >= 5 {
+        return Err(Error::NonCanonicalTrits {
+            offset: trits.len() - 5,
+        });
+    }
+
+    decode_radix_minimal(&trits[..significant], len)
+}
+
+fn decode_radix_minimal(trits: &[BalancedTrit], len: usize) -> Result<Vec<u8>, Error> {
+    if trits.last() == Some(&BalancedTrit::MinusOne) {
+        return Err(Error::NonCanonicalTrits {
+            offset: trits.len() - 1,
+        });
+    }
+
+    // Horner's scheme from the most significant trit; since that trit is 1, every partial
+    // value is positive.
+    let mut number = vec![0u8; len];
+
+    for trit in trits.iter().rev() {
+        let mut carry = *trit as i16;
+        for digit in number.iter_mut().rev() {
+            let value = *digit as i16 * 3 + carry;
+            *digit = value.rem_euclid(256) as u8;
+            carry = value.div_euclid(256);
+        }
+        if carry != 0 {
+            return Err(Error::ByteOverflow { len });
+        }
+    }
+
+    Ok(number)
+}
+
+fn increment(number: &mut Vec<u8>) {
+    for digit in number.iter_mut() {
+        let (value, overflow) = digit.overflowing_add(1);
+        *digit = value;
+        if !overflow {
+            return;
+        }
+    }
+    number.push(1);
+}
+
+fn balanced_digit(value: &mut i16) -> BalancedTrit {
+    let trit = match value.rem_euclid(3) {
+        0 => BalancedTrit::Zero,
+        1 => BalancedTrit::PlusOne,
+        _ => BalancedTrit::MinusOne,
+    };
+    *value = (*value - trit as i16) / 3;
+    trit
+}
+
+#[allow(clippy::manual_is_multiple_of)]
+fn pad_to_t5b1(mut trits: T1B1) -> T5B1 {
+    while trits.len() % 5 != 0 {
+        trits.push_internal(BalancedTrit::Zero);
+    }
+    trits.into()
+}
+
+fn check_padding(trits: &[BalancedTrit], len: usize) -> Result<(), Error> {
+    if trits.len() - len >= 5 {
+        return Err(Error::NonCanonicalTrits { offset: len });
+    }
+    match trits[len..].iter().position(|t| *t != BalancedTrit::Zero) {
+        Some(i) => Err(Error::NonCanonicalTrits { offset: len + i }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: [&[u8]; 6] = [
+        b"",
+        b"\x00",
+        b"\x00\x00\x01",
+        b"\xff",
+        b"minitri",
+        b"\x80\x7f\x00\xff\x01",
+    ];
+
+    #[test]
+    fn b1t6_known_values() {
+        assert_eq!("000000", encode_b1t6(&[0]).to_string());
+        assert_eq!("100000", encode_b1t6(&[1]).to_string());
+        assert_eq!("-00000", encode_b1t6(&[0xff]).to_string());
+        assert_eq!("10---1", encode_b1t6(&[0x7f]).to_string());
+        assert_eq!("1-111-", encode_b1t6(&[0x80]).to_string());
+    }
+
+    #[test]
+    fn b1t6_round_trip() {
+        for bytes in SAMPLES.iter() {
+            assert_eq!(Ok(bytes.to_vec()), decode_b1t6(&encode_b1t6(bytes)));
+            assert_eq!(
+                Ok(bytes.to_vec()),
+                decode_b1t6_t5b1(&encode_b1t6_t5b1(bytes))
+            );
+        }
+
+        let all = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(Ok(all.clone()), decode_b1t6(&encode_b1t6(&all)));
+    }
+
+    #[test]
+    fn b1t6_errors() {
+        assert_eq!(
+            Err(Error::InvalidTritLength {
+                len: 5,
+                multiple: 6
+            }),
+            decode_b1t6(&"00000".into())
+        );
+        // 121 + 243 = 364 doesn't fit into an i8.
+        assert_eq!(
+            Err(Error::NonCanonicalTrits { offset: 6 }),
+            decode_b1t6(&"000000111111".into())
+        );
+        // 6 trits plus 4 non-zero padding trits.
+        let t5b1: T5B1 = T1B1::from("0000001000").into();
+        assert_eq!(
+            Err(Error::NonCanonicalTrits { offset: 6 }),
+            decode_b1t6_t5b1(&t5b1)
+        );
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 1,
+                value: 243
+            }),
+            decode_b1t6_t5b1(&T5B1::from_u8(&[0, 243]))
+        );
+    }
+
+    #[test]
+    fn radix_known_values() {
+        assert_eq!("", encode_radix(&[0, 0]).to_string());
+        assert_eq!("1", encode_radix(&[1]).to_string());
+        assert_eq!("-1", encode_radix(&[2]).to_string());
+        assert_eq!("0-1", encode_radix(&[6]).to_string());
+        assert_eq!("011001", encode_radix(&[255]).to_string());
+        assert_eq!("111001", encode_radix(&[1, 0]).to_string());
+    }
+
+    #[test]
+    fn radix_round_trip() {
+        for bytes in SAMPLES.iter() {
+            let len = bytes.len();
+            assert_eq!(Ok(bytes.to_vec()), decode_radix(&encode_radix(bytes), len));
+            assert_eq!(
+                Ok(bytes.to_vec()),
+                decode_radix_t5b1(&encode_radix_t5b1(bytes), len)
+            );
+        }
+    }
+
+    #[test]
+    fn radix_is_minimal() {
+        // n balanced trits reach (3^n - 1) / 2, which first exceeds 2^64 - 1 for n = 42.
+        assert_eq!(42, encode_radix(&[0xff; 8]).len());
+        assert!(encode_radix(&[0xff; 8]).len() < encode_b1t6(&[0xff; 8]).len());
+    }
+
+    #[test]
+    fn radix_errors() {
+        assert_eq!(
+            Err(Error::NonCanonicalTrits { offset: 1 }),
+            decode_radix(&"10".into(), 1)
+        );
+        assert_eq!(
+            Err(Error::NonCanonicalTrits { offset: 1 }),
+            decode_radix(&"1-".into(), 1)
+        );
+        assert_eq!(
+            Err(Error::ByteOverflow { len: 1 }),
+            decode_radix(&encode_radix(&[1, 0]), 1)
+        );
+        let t5b1: T5B1 = T1B1::from("1000000000").into();
+        assert_eq!(
+            Err(Error::NonCanonicalTrits { offset: 5 }),
+            decode_radix_t5b1(&t5b1, 1)
+        );
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 0,
+                value: 255
+            }),
+            decode_radix_t5b1(&T5B1::from_u8(&[255]), 1)
+        );
+    }
+}
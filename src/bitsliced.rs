@@ -0,0 +1,333 @@
+//! Bitsliced trit storage, where every trit position holds one trit of many independent
+//! sequences (lanes) in a pair of machine words.
+//!
+//! A trit is stored as one bit in the `pos` plane (+1) or the `neg` plane (-1); zero sets
+//! neither. Element-wise ternary operations then become a handful of bitwise operations that
+//! process all lanes at once, which is what batched hashers and nonce searchers need.
+
+use crate::encodings::{t1b1::T1B1, Encoding};
+use crate::trit::BalancedTrit;
+
+use std::ops::{Add, Mul, Neg, Range};
+
+macro_rules! bitsliced {
+    ($name:ident, $lane:ident, $word:ty) => {
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub struct $name {
+            pos: Vec<$word>,
+            neg: Vec<$word>,
+        }
+
+        impl $name {
+            /// Number of independent sequences stored side by side.
+            pub const LANES: usize = <$word>::BITS as usize;
+
+            /// Creates `len` trits per lane, all set to zero.
+            pub fn new(len: usize) -> Self {
+                Self {
+                    pos: vec![0; len],
+                    neg: vec![0; len],
+                }
+            }
+
+            /// Transposes up to `LANES` sequences of equal length into bitsliced form. Missing
+            /// lanes are filled with zeros.
+            pub fn from_t1b1(sequences: &[T1B1]) -> Self {
+                if sequences.len() > Self::LANES {
+                    panic!("Too many sequences");
+                }
+
+                let len = sequences.first().map_or(0, |s| s.len());
+                let mut sliced = Self::new(len);
+
+                for (lane, sequence) in sequences.iter().enumerate() {
+                    if sequence.len() != len {
+                        panic!("Sequences must have equal length");
+                    }
+                    for index in 0..len {
+                        sliced.set(index, lane, sequence.get(index));
+                    }
+                }
+
+                sliced
+            }
+
+            /// Transposes all lanes back into `LANES` separate sequences.
+            pub fn to_t1b1(&self) -> Vec<T1B1> {
+                (0..Self::LANES)
+                    .map(|lane| self.lane(lane).into())
+                    .collect()
+            }
+
+            /// Number of trits per lane.
+            pub fn len(&self) -> usize {
+                self.pos.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.pos.is_empty()
+            }
+
+            pub fn get(&self, index: usize, lane: usize) -> BalancedTrit {
+                if lane >= Self::LANES {
+                    panic!("Invalid lane");
+                }
+                let bit = 1 << lane;
+                if self.pos[index] & bit != 0 {
+                    BalancedTrit::PlusOne
+                } else if self.neg[index] & bit != 0 {
+                    BalancedTrit::MinusOne
+                } else {
+                    BalancedTrit::Zero
+                }
+            }
+
+            pub fn set(&mut self, index: usize, lane: usize, trit: BalancedTrit) {
+                if lane >= Self::LANES {
+                    panic!("Invalid lane");
+                }
+                let bit: $word = 1 << lane;
+                self.pos[index] &= !bit;
+                self.neg[index] &= !bit;
+
+                match trit {
+                    BalancedTrit::PlusOne => self.pos[index] |= bit,
+                    BalancedTrit::MinusOne => self.neg[index] |= bit,
+                    BalancedTrit::Zero => (),
+                }
+            }
+
+            /// Sets the trit at `index` to the same value in every lane.
+            pub fn fill(&mut self, index: usize, trit: BalancedTrit) {
+                self.pos[index] = if trit == BalancedTrit::PlusOne { !0 } else { 0 };
+                self.neg[index] = if trit == BalancedTrit::MinusOne {
+                    !0
+                } else {
+                    0
+                };
+            }
+
+            /// A mask of the lanes whose trits in `range` are all zero.
+            pub fn zero_lanes(&self, range: Range<usize>) -> $word {
+                self.pos[range.clone()]
+                    .iter()
+                    .zip(&self.neg[range])
+                    .fold(!0, |zeros, (pos, neg)| zeros & !(pos | neg))
+            }
+
+            /// The `pos` and `neg` planes at `index`, for kernels that combine positions
+            /// instead of working element-wise, like Curl.
+            pub fn planes(&self, index: usize) -> ($word, $word) {
+                (self.pos[index], self.neg[index])
+            }
+
+            pub fn set_planes(&mut self, index: usize, pos: $word, neg: $word) {
+                self.pos[index] = pos;
+                self.neg[index] = neg;
+            }
+
+            /// Borrows a single lane as a trit sequence.
+            pub fn lane(&self, lane: usize) -> $lane<'_> {
+                if lane >= Self::LANES {
+                    panic!("Invalid lane");
+                }
+                $lane { sliced: self, lane }
+            }
+
+            /// Element-wise minimum of two trits.
+            pub fn min(&self, other: &Self) -> Self {
+                self.zip(other, |ap, an, bp, bn| (ap & bp, an | bn))
+            }
+
+            /// Element-wise maximum of two trits.
+            pub fn max(&self, other: &Self) -> Self {
+                self.zip(other, |ap, an, bp, bn| (ap | bp, an & bn))
+            }
+
+            fn zip(
+                &self,
+                other: &Self,
+                op: impl Fn($word, $word, $word, $word) -> ($word, $word),
+            ) -> Self {
+                if self.len() != other.len() {
+                    panic!("Operands must have equal length");
+                }
+
+                let mut result = Self::new(self.len());
+
+                for i in 0..self.len() {
+                    let (pos, neg) = op(self.pos[i], self.neg[i], other.pos[i], other.neg[i]);
+                    result.pos[i] = pos;
+                    result.neg[i] = neg;
+                }
+
+                result
+            }
+        }
+
+        impl<'a> Neg for &'a $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name {
+                    pos: self.neg.clone(),
+                    neg: self.pos.clone(),
+                }
+            }
+        }
+
+        /// Element-wise sum modulo 3, i.e. `1 + 1 = -1`.
+        impl<'a> Add for &'a $name {
+            type Output = $name;
+
+            fn add(self, other: Self) -> $name {
+                self.zip(other, |ap, an, bp, bn| {
+                    let az = !(ap | an);
+                    let bz = !(bp | bn);
+                    (
+                        (ap & bz) | (az & bp) | (an & bn),
+                        (an & bz) | (az & bn) | (ap & bp),
+                    )
+                })
+            }
+        }
+
+        /// Element-wise product.
+        impl<'a> Mul for &'a $name {
+            type Output = $name;
+
+            fn mul(self, other: Self) -> $name {
+                self.zip(other, |ap, an, bp, bn| {
+                    ((ap & bp) | (an & bn), (ap & bn) | (an & bp))
+                })
+            }
+        }
+
+        /// A single lane of a bitsliced container, which can be pushed into any `TritVec`.
+        #[derive(Clone, Copy, Debug)]
+        pub struct $lane<'a> {
+            sliced: &'a $name,
+            lane: usize,
+        }
+
+        impl<'a> $lane<'a> {
+            pub fn len(&self) -> usize {
+                self.sliced.len()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.sliced.is_empty()
+            }
+
+            pub fn get(&self, index: usize) -> BalancedTrit {
+                self.sliced.get(index, self.lane)
+            }
+        }
+
+        impl<'a> From<$lane<'a>> for T1B1 {
+            fn from(lane: $lane<'a>) -> T1B1 {
+                let mut trits = T1B1::with_capacity(lane.len());
+
+                for index in 0..lane.len() {
+                    trits.push_internal(lane.get(index));
+                }
+
+                trits
+            }
+        }
+    };
+}
+
+bitsliced!(BitSliced64, Lane64, u64);
+bitsliced!(BitSliced128, Lane128, u128);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::t5b1::T5B1;
+    use crate::tritvec::TritVec;
+
+    fn sequences(n: usize, len: usize) -> Vec<T1B1> {
+        (0..n)
+            .map(|lane| {
+                let trits = (0..len)
+                    .map(|i| ((lane * 7 + i * 5) % 3) as i8 - 1)
+                    .collect::<Vec<i8>>();
+                T1B1::from_i8(&trits)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn transpose_round_trip() {
+        let input = sequences(64, 243);
+        let sliced = BitSliced64::from_t1b1(&input);
+        let output = sliced.to_t1b1();
+
+        for (a, b) in input.iter().zip(output.iter()) {
+            assert_eq!(a.to_string(), b.to_string());
+        }
+
+        let input = sequences(100, 27);
+        let sliced = BitSliced128::from_t1b1(&input);
+        let output = sliced.to_t1b1();
+
+        for (a, b) in input.iter().zip(output.iter()) {
+            assert_eq!(a.to_string(), b.to_string());
+        }
+        assert_eq!("0".repeat(27), output[127].to_string());
+    }
+
+    fn lane0(sliced: &BitSliced64) -> String {
+        T1B1::from(sliced.lane(0)).to_string()
+    }
+
+    #[test]
+    fn element_wise_ops() {
+        let a = BitSliced64::from_t1b1(&["---000111".into()]);
+        let b = BitSliced64::from_t1b1(&["-01-01-01".into()]);
+
+        assert_eq!("111000---", lane0(&-&a));
+        assert_eq!("1-0-0101-", lane0(&(&a + &b)));
+        assert_eq!("10-000-01", lane0(&(&a * &b)));
+        assert_eq!("----00-01", lane0(&a.min(&b)));
+        assert_eq!("-01001111", lane0(&a.max(&b)));
+    }
+
+    #[test]
+    fn zero_lanes() {
+        let sliced = BitSliced64::from_t1b1(&["0001".into(), "1000".into(), "0000".into()]);
+
+        assert_eq!(!0 ^ 0b001, sliced.zero_lanes(1..4));
+        assert_eq!(!0 ^ 0b010, sliced.zero_lanes(0..3));
+        assert_eq!(!0, sliced.zero_lanes(1..3));
+    }
+
+    #[test]
+    fn lane_into_tritvec() {
+        let sliced = BitSliced64::from_t1b1(&sequences(3, 10));
+
+        let mut vec: TritVec<T5B1> = TritVec::new();
+        vec.push(sliced.lane(2));
+
+        assert_eq!(2, vec.len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_for_unequal_lengths() {
+        let _ = BitSliced64::from_t1b1(&["10-".into(), "10".into()]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_for_invalid_lane() {
+        let _ = BitSliced64::new(1).get(0, 64);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_for_invalid_lane_on_set() {
+        BitSliced64::new(1).set(0, 64, BalancedTrit::PlusOne);
+    }
+}
@@ -0,0 +1,151 @@
+//! The Curl-P-81 sponge, as used for transaction hashes and proof of work.
+
+use crate::encodings::{t1b1::T1B1, Encoding};
+use crate::trit::BalancedTrit;
+
+pub const HASH_LENGTH: usize = 243;
+pub const STATE_LENGTH: usize = 3 * HASH_LENGTH;
+pub const ROUNDS: usize = 81;
+
+const TRUTH_TABLE: [i8; 11] = [1, 0, -1, 2, 1, -1, 0, 2, -1, 1, 0];
+
+#[derive(Clone, Debug)]
+pub struct CurlP81 {
+    state: [i8; STATE_LENGTH],
+}
+
+impl CurlP81 {
+    pub fn new() -> Self {
+        Self {
+            state: [0; STATE_LENGTH],
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.state = [0; STATE_LENGTH];
+    }
+
+    /// NOTE: the number of trits must be a multiple of `HASH_LENGTH`.
+    pub fn absorb(&mut self, trits: &T1B1) {
+        self.absorb_slice(trits.as_slice());
+    }
+
+    #[allow(clippy::manual_is_multiple_of)]
+    pub(crate) fn absorb_slice(&mut self, trits: &[BalancedTrit]) {
+        if trits.len() % HASH_LENGTH != 0 {
+            panic!("Invalid number of trits to absorb");
+        }
+
+        for chunk in trits.chunks(HASH_LENGTH) {
+            for (s, t) in self.state.iter_mut().zip(chunk) {
+                *s = *t as i8;
+            }
+            self.transform();
+        }
+    }
+
+    pub fn squeeze(&mut self) -> T1B1 {
+        let mut hash = T1B1::with_capacity(HASH_LENGTH);
+
+        for trit in &self.state[..HASH_LENGTH] {
+            hash.push_internal((*trit).into());
+        }
+        self.transform();
+
+        hash
+    }
+
+    /// Absorbs the trits and squeezes a single hash out of a fresh sponge.
+    pub fn hash(trits: &T1B1) -> T1B1 {
+        let mut curl = Self::new();
+        curl.absorb(trits);
+        curl.squeeze()
+    }
+
+    pub(crate) fn state(&self) -> &[i8; STATE_LENGTH] {
+        &self.state
+    }
+
+    fn transform(&mut self) {
+        let mut scratch = [0i8; STATE_LENGTH];
+
+        for _ in 0..ROUNDS {
+            scratch.copy_from_slice(&self.state);
+
+            let mut index = 0;
+            for trit in self.state.iter_mut() {
+                let prev = index;
+                index = next_index(index);
+                let i = scratch[prev] + (scratch[index] << 2) + 5;
+                *trit = TRUTH_TABLE[i as usize];
+            }
+        }
+    }
+}
+
+impl Default for CurlP81 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Position of the second input trit of the next state trit.
+pub(crate) fn next_index(index: usize) -> usize {
+    if index < 365 {
+        index + 364
+    } else {
+        index - 365
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::t3b1::T3B1;
+
+    /// The Curl-P-81 vector of the IOTA reference libraries.
+    #[test]
+    fn known_answer() {
+        let input: T3B1 =
+            "EMIDYNHBWMBCXVDEFOFWINXTERALUKYYPPHKP9JJFGJEIUY9MUDVNFZHMMWZUYUSWAIOWEVTHNWMHANBH"
+                .into();
+        let hash: T3B1 = CurlP81::hash(&input.into()).into();
+
+        assert_eq!(
+            "AQBOPUMJMGVHFOXSMUAGZNACKUTISDPBSILMRAGIGRXXS9JJTLIKZUW9BCJWKSTFBDSBLNVEEGVGAMSSM",
+            hash.to_string()
+        );
+    }
+
+    #[test]
+    fn hash_length() {
+        let trits = T1B1::from_i8(&[1; 2 * HASH_LENGTH]);
+        assert_eq!(HASH_LENGTH, CurlP81::hash(&trits).len());
+    }
+
+    #[test]
+    fn hash_is_deterministic_and_input_sensitive() {
+        let mut input = vec![0i8; HASH_LENGTH];
+        let a = CurlP81::hash(&T1B1::from_i8(&input)).to_string();
+        let b = CurlP81::hash(&T1B1::from_i8(&input)).to_string();
+        input[0] = 1;
+        let c = CurlP81::hash(&T1B1::from_i8(&input)).to_string();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn squeeze_continues_the_sponge() {
+        let mut curl = CurlP81::new();
+        curl.absorb(&T1B1::from_i8(&[-1; HASH_LENGTH]));
+
+        assert_ne!(curl.squeeze().to_string(), curl.squeeze().to_string());
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_for_partial_chunk() {
+        CurlP81::new().absorb(&T1B1::from_i8(&[0; 10]));
+    }
+}
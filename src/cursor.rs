@@ -0,0 +1,284 @@
+//! Cursors for reading and writing consecutive fields of trits, e.g. the parts of a transaction.
+
+use crate::encodings::{iter::Trits, t1b1::T1B1, t3b1::T3B1, Encoding};
+use crate::error::Error;
+use crate::trit::BalancedTrit;
+use crate::tryte::BalancedTryte;
+
+use std::iter::FromIterator;
+
+/// The largest width of an integer field, since (3^40 - 1) / 2 still fits into an `i64`.
+pub const MAX_INT_WIDTH: usize = 40;
+
+/// Reads fields from the trits of any encoding, front to back.
+#[derive(Clone, Debug)]
+pub struct TritReader<'a> {
+    trits: Trits<'a>,
+    position: usize,
+}
+
+impl<'a> TritReader<'a> {
+    pub fn new<T: Encoding>(encoding: &'a T) -> Self {
+        Self {
+            trits: encoding.trits(),
+            position: 0,
+        }
+    }
+
+    /// The number of trits read or skipped so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.trits.len()
+    }
+
+    pub fn read_trit(&mut self) -> Result<BalancedTrit, Error> {
+        self.check(1)?;
+        self.position += 1;
+        Ok(self.trits.next().expect("checked length"))
+    }
+
+    pub fn read_trits(&mut self, n: usize) -> Result<T1B1, Error> {
+        self.check(n)?;
+        self.position += n;
+        Ok(self.trits.by_ref().take(n).collect())
+    }
+
+    /// Reads `n` trytes, i.e. `3 * n` trits.
+    pub fn read_trytes(&mut self, n: usize) -> Result<T3B1, Error> {
+        let len = n.checked_mul(3).ok_or(Error::UnexpectedEnd {
+            offset: self.position,
+            requested: usize::MAX,
+            available: self.remaining(),
+        })?;
+        self.check(len)?;
+        self.position += len;
+
+        let trits = &mut self.trits;
+        Ok((0..n)
+            .map(|_| {
+                let mut tryte = [BalancedTrit::Zero; 3];
+                tryte.iter_mut().for_each(|t| *t = trits.next().unwrap());
+                BalancedTryte::from_trits(tryte)
+            })
+            .collect())
+    }
+
+    /// Reads a signed integer of `width` trits, least significant trit first.
+    ///
+    /// NOTE: panics if `width` is above `MAX_INT_WIDTH`.
+    pub fn read_i64(&mut self, width: usize) -> Result<i64, Error> {
+        if width > MAX_INT_WIDTH {
+            panic!("Invalid integer width");
+        }
+        self.check(width)?;
+        self.position += width;
+
+        let mut value = 0i64;
+        let mut weight = 1i64;
+        for trit in self.trits.by_ref().take(width) {
+            value += trit as i64 * weight;
+            weight = weight.saturating_mul(3);
+        }
+
+        Ok(value)
+    }
+
+    pub fn skip(&mut self, n: usize) -> Result<(), Error> {
+        self.check(n)?;
+        self.position += n;
+        if n > 0 {
+            self.trits.nth(n - 1);
+        }
+        Ok(())
+    }
+
+    fn check(&self, requested: usize) -> Result<(), Error> {
+        if requested > self.remaining() {
+            Err(Error::UnexpectedEnd {
+                offset: self.position,
+                requested,
+                available: self.remaining(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Appends fields to a sequence of trits, optionally limited to a fixed length.
+#[derive(Clone, Debug, Default)]
+pub struct TritWriter {
+    trits: T1B1,
+    limit: Option<usize>,
+}
+
+impl TritWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fails every write that would exceed `len` trits.
+    pub fn with_limit(len: usize) -> Self {
+        Self {
+            trits: T1B1::with_capacity(len),
+            limit: Some(len),
+        }
+    }
+
+    /// The number of trits written so far.
+    pub fn position(&self) -> usize {
+        self.trits.len()
+    }
+
+    pub fn write_trit(&mut self, trit: BalancedTrit) -> Result<(), Error> {
+        self.check(1)?;
+        self.trits.push_internal(trit);
+        Ok(())
+    }
+
+    pub fn write_trits<T: Encoding>(&mut self, trits: &T) -> Result<(), Error> {
+        self.check(trits.trits().len())?;
+        trits.trits().for_each(|t| self.trits.push_internal(t));
+        Ok(())
+    }
+
+    pub fn write_trytes(&mut self, trytes: &T3B1) -> Result<(), Error> {
+        self.write_trits(trytes)
+    }
+
+    /// Writes a signed integer as `width` trits, least significant trit first, and fails if it
+    /// doesn't fit.
+    pub fn write_i64(&mut self, value: i64, width: usize) -> Result<(), Error> {
+        self.check(width)?;
+
+        let mut trits = Vec::with_capacity(width);
+        let mut rest = value as i128;
+        for _ in 0..width {
+            let trit = (rest + 1).rem_euclid(3) - 1;
+            trits.push(BalancedTrit::from(trit as i8));
+            rest = (rest - trit) / 3;
+        }
+        if rest != 0 {
+            return Err(Error::IntOverflow { value, width });
+        }
+
+        trits.into_iter().for_each(|t| self.trits.push_internal(t));
+        Ok(())
+    }
+
+    /// Writes `n` zero trits.
+    pub fn pad(&mut self, n: usize) -> Result<(), Error> {
+        self.check(n)?;
+        (0..n).for_each(|_| self.trits.push_internal(BalancedTrit::Zero));
+        Ok(())
+    }
+
+    /// Returns the written trits in any encoding, padded like its `FromIterator`.
+    pub fn finish<T: FromIterator<BalancedTrit>>(self) -> T {
+        self.trits.into_iter().collect()
+    }
+
+    fn check(&self, requested: usize) -> Result<(), Error> {
+        match self.limit {
+            Some(limit) if self.position() + requested > limit => Err(Error::UnexpectedEnd {
+                offset: self.position(),
+                requested,
+                available: limit - self.position(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::t5b1::T5B1;
+
+    #[test]
+    fn read_fields() {
+        let trytes: T3B1 = "MINI9TRIABC".into();
+        let mut reader = TritReader::new(&trytes);
+
+        assert_eq!("MI", reader.read_trytes(2).unwrap().to_string());
+        assert_eq!(6, reader.position());
+        assert_eq!(Ok(-13), reader.read_i64(3));
+        reader.skip(3).unwrap();
+        assert_eq!("000", reader.read_trits(3).unwrap().to_string());
+        assert_eq!(Ok(BalancedTrit::MinusOne), reader.read_trit());
+        assert_eq!(17, reader.remaining());
+        assert_eq!(
+            Err(Error::UnexpectedEnd {
+                offset: 16,
+                requested: 18,
+                available: 17
+            }),
+            reader.read_trits(18).map(|t| t.len())
+        );
+        assert_eq!(
+            Err(Error::UnexpectedEnd {
+                offset: 16,
+                requested: usize::MAX,
+                available: 17
+            }),
+            reader.read_trytes(usize::MAX / 2).map(|t| t.len())
+        );
+        assert_eq!(16, reader.position());
+        reader.skip(17).unwrap();
+        assert_eq!(0, reader.remaining());
+    }
+
+    #[test]
+    fn write_then_read() {
+        let mut writer = TritWriter::with_limit(30);
+        writer.write_trytes(&"MINI".into()).unwrap();
+        writer.write_i64(-1000, 9).unwrap();
+        writer.write_trits(&T1B1::from("1-")).unwrap();
+        writer.pad(7).unwrap();
+        assert_eq!(30, writer.position());
+
+        assert_eq!(
+            Err(Error::UnexpectedEnd {
+                offset: 30,
+                requested: 1,
+                available: 0
+            }),
+            writer.write_trit(BalancedTrit::Zero)
+        );
+
+        let bytes: T5B1 = writer.finish();
+        let mut reader = TritReader::new(&bytes);
+
+        assert_eq!("MINI", reader.read_trytes(4).unwrap().to_string());
+        assert_eq!(Ok(-1000), reader.read_i64(9));
+        assert_eq!("1-", reader.read_trits(2).unwrap().to_string());
+        assert_eq!(Ok(0), reader.read_i64(7));
+    }
+
+    #[test]
+    fn integer_limits() {
+        let max = ((3i128.pow(40) - 1) / 2) as i64;
+        let mut writer = TritWriter::new();
+
+        writer.write_i64(max, 40).unwrap();
+        writer.write_i64(-max, 40).unwrap();
+        writer.write_i64(13, 3).unwrap();
+        assert_eq!(
+            Err(Error::IntOverflow {
+                value: 14,
+                width: 3
+            }),
+            writer.write_i64(14, 3)
+        );
+        assert_eq!(83, writer.position());
+
+        let trits: T1B1 = writer.finish();
+        let mut reader = TritReader::new(&trits);
+        assert_eq!(Ok(max), reader.read_i64(40));
+        assert_eq!(Ok(-max), reader.read_i64(40));
+        assert_eq!(Ok(13), reader.read_i64(3));
+    }
+}
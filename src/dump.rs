@@ -0,0 +1,183 @@
+//! A hexdump-like view of trits, for reading long sequences such as transactions in logs.
+//!
+//! ```text
+//! 000000  111 001 --- 001 000 -1- 00- 001 100  |MINI9TRIA|   13   9 -13   9   0  -7  -9   9   1
+//! 000027  -10 010                              |BC|           2   3
+//! ```
+//!
+//! Every line starts with the offset of its first trit, followed by the groups of trits, the
+//! trytes of the line and the integer value of every group, least significant trit first.
+
+use crate::alphabet::TritAlphabet;
+use crate::encodings::{iter::Trits, Encoding};
+use crate::trit::BalancedTrit;
+use crate::tryte::BalancedTryte;
+
+use std::fmt;
+use std::fmt::Write;
+
+#[derive(Clone, Debug)]
+pub struct TritDump<'a> {
+    trits: Trits<'a>,
+    group: usize,
+    per_line: usize,
+    alphabet: TritAlphabet,
+    trytes: bool,
+    values: bool,
+}
+
+impl<'a> TritDump<'a> {
+    /// Groups of 3 trits, 9 groups per line, with trytes and values.
+    pub fn new<T: Encoding>(encoding: &'a T) -> Self {
+        Self {
+            trits: encoding.trits(),
+            group: 3,
+            per_line: 9,
+            alphabet: TritAlphabet::DEFAULT,
+            trytes: true,
+            values: true,
+        }
+    }
+
+    /// NOTE: the width has to be in 1..=39, so that the value of a group fits into an `i64`.
+    pub fn group(mut self, width: usize) -> Self {
+        if width == 0 || width > 39 {
+            panic!("Invalid group width");
+        }
+        self.group = width;
+        self
+    }
+
+    pub fn per_line(mut self, groups: usize) -> Self {
+        self.per_line = groups.max(1);
+        self
+    }
+
+    pub fn alphabet(mut self, alphabet: TritAlphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    pub fn trytes(mut self, yes: bool) -> Self {
+        self.trytes = yes;
+        self
+    }
+
+    pub fn values(mut self, yes: bool) -> Self {
+        self.values = yes;
+        self
+    }
+
+    fn write_line(&self, line: &mut String, offset: usize, trits: &[BalancedTrit]) -> fmt::Result {
+        let line_len = self.group * self.per_line;
+        let max = (3i64.pow(self.group as u32) - 1) / 2;
+        let value_width = (-max).to_string().len();
+
+        write!(line, "{:06} ", offset)?;
+
+        for i in 0..self.per_line {
+            line.push(' ');
+            let group = trits.chunks(self.group).nth(i).unwrap_or(&[]);
+            for trit in group {
+                line.push(self.alphabet.to_char(*trit));
+            }
+            line.extend(std::iter::repeat_n(' ', self.group - group.len()));
+        }
+
+        if self.trytes {
+            line.push_str("  |");
+            for chunk in trits.chunks(3) {
+                let mut tryte = [BalancedTrit::Zero; 3];
+                tryte[..chunk.len()].copy_from_slice(chunk);
+                line.push(BalancedTryte::from_trits(tryte).to_char());
+            }
+            line.push('|');
+            let missing = line_len.div_ceil(3) - trits.len().div_ceil(3);
+            line.extend(std::iter::repeat_n(' ', missing));
+        }
+
+        if self.values {
+            line.push(' ');
+            for group in trits.chunks(self.group) {
+                let value = group
+                    .iter()
+                    .rev()
+                    .fold(0i64, |acc, trit| acc * 3 + *trit as i64);
+                write!(line, " {:>width$}", value, width = value_width)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for TritDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let trits = self.trits.clone().collect::<Vec<BalancedTrit>>();
+        let line_len = self.group * self.per_line;
+        let mut line = String::new();
+
+        for (i, chunk) in trits.chunks(line_len).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            line.clear();
+            self.write_line(&mut line, i * line_len, chunk)?;
+            write!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::{t1b1::T1B1, t3b1::T3B1, t5b1::T5B1};
+
+    #[test]
+    fn default_layout() {
+        let trytes: T3B1 = "MINI9TRIABC".into();
+
+        let expected = "\
+000000  111 001 --- 001 000 -1- 00- 001 100  |MINI9TRIA|   13   9 -13   9   0  -7  -9   9   1
+000027  -10 010                              |BC|           2   3";
+        assert_eq!(expected, TritDump::new(&trytes).to_string());
+    }
+
+    #[test]
+    fn configurable_groups() {
+        let trits: T1B1 = "10-01-110-".into();
+
+        let dump = TritDump::new(&trits)
+            .group(5)
+            .per_line(1)
+            .trytes(false)
+            .alphabet(TritAlphabet::SIGNS);
+        assert_eq!("000000  +0-0+    73\n000005  -++0-   -70", dump.to_string());
+
+        let dump = TritDump::new(&trits).values(false).per_line(2);
+        assert_eq!(
+            "000000  10- 01-  |SU|\n000006  110 -    |DZ|",
+            dump.to_string()
+        );
+    }
+
+    #[test]
+    fn empty_and_packed() {
+        assert_eq!("", TritDump::new(&T1B1::new()).to_string());
+
+        let bytes: T5B1 = T1B1::from("10000-----").into();
+        assert_eq!(
+            "000000  100 00- --- -                        |ARNZ|         1  -9 -13  -1",
+            TritDump::new(&bytes).to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_for_invalid_group_width() {
+        let trits = T1B1::new();
+        let _ = TritDump::new(&trits).group(40);
+    }
+}
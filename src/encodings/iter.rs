@@ -0,0 +1,285 @@
+//! Borrowing iterators over the trits and trytes of any encoding.
+
+use crate::trit::BalancedTrit;
+use crate::tryte::BalancedTryte;
+
+use std::fmt;
+use std::iter::FusedIterator;
+
+/// Random access to the trits of a sequence, which is all `Trits` and `Trytes` need. Every
+/// encoding implements it in its own module, next to its storage layout.
+pub trait TritAccess {
+    /// The number of trits, e.g. 5 times `len` for T5B1.
+    fn trit_len(&self) -> usize;
+
+    /// The trit at `index`, least significant first. Panics if `index` is out of bounds.
+    fn trit(&self, index: usize) -> BalancedTrit;
+
+    /// The trit at `index`, or `None` if its storage is invalid, e.g. a T5B1 byte above 242 that
+    /// `trit` panics on. Only encodings that can hold invalid storage need to override it.
+    fn checked_trit(&self, index: usize) -> Option<BalancedTrit> {
+        Some(self.trit(index))
+    }
+
+    /// The trits `3 * index..3 * index + 3`, padded with zero trits past the end. Encodings
+    /// that store trytes can return them directly.
+    fn tryte(&self, index: usize) -> BalancedTryte {
+        let len = self.trit_len();
+        let mut trits = [BalancedTrit::Zero; 3];
+
+        for (j, trit) in trits.iter_mut().enumerate() {
+            let i = index * 3 + j;
+            if i < len {
+                *trit = self.trit(i);
+            }
+        }
+
+        BalancedTryte::from_trits(trits)
+    }
+}
+
+/// Yields the trits of an encoding, see `Encoding::trits`.
+#[derive(Clone)]
+pub struct Trits<'a> {
+    source: &'a dyn TritAccess,
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Trits<'a> {
+    pub fn new(source: &'a dyn TritAccess) -> Self {
+        Self {
+            source,
+            front: 0,
+            back: source.trit_len(),
+        }
+    }
+}
+
+impl fmt::Debug for Trits<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Trits")
+            .field("front", &self.front)
+            .field("back", &self.back)
+            .finish()
+    }
+}
+
+impl Iterator for Trits<'_> {
+    type Item = BalancedTrit;
+
+    fn next(&mut self) -> Option<BalancedTrit> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.source.trit(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<BalancedTrit> {
+        self.front = self.back.min(self.front.saturating_add(n));
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for Trits<'_> {
+    fn next_back(&mut self) -> Option<BalancedTrit> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.source.trit(self.back))
+    }
+}
+
+impl ExactSizeIterator for Trits<'_> {}
+
+impl FusedIterator for Trits<'_> {}
+
+/// Yields the trits of an encoding in groups of 3, see `Encoding::trytes`. If the number of
+/// trits is not a multiple of 3, the last tryte is padded with zero trits.
+#[derive(Clone)]
+pub struct Trytes<'a> {
+    source: &'a dyn TritAccess,
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Trytes<'a> {
+    pub fn new(source: &'a dyn TritAccess) -> Self {
+        Self {
+            source,
+            front: 0,
+            back: source.trit_len().div_ceil(3),
+        }
+    }
+}
+
+impl fmt::Debug for Trytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Trytes")
+            .field("front", &self.front)
+            .field("back", &self.back)
+            .finish()
+    }
+}
+
+impl Iterator for Trytes<'_> {
+    type Item = BalancedTryte;
+
+    fn next(&mut self) -> Option<BalancedTryte> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.source.tryte(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<BalancedTryte> {
+        self.front = self.back.min(self.front.saturating_add(n));
+        self.next()
+    }
+}
+
+impl DoubleEndedIterator for Trytes<'_> {
+    fn next_back(&mut self) -> Option<BalancedTryte> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.source.tryte(self.back))
+    }
+}
+
+impl ExactSizeIterator for Trytes<'_> {}
+
+impl FusedIterator for Trytes<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::TritAccess;
+    use crate::encodings::{t1b1::T1B1, t3b1::T3B1, t5b1::T5B1, Encoding};
+    use crate::trit::BalancedTrit;
+    use crate::tryte::BalancedTryte;
+
+    fn string<T: ToString>(items: impl Iterator<Item = T>) -> String {
+        items.map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn trits_of_every_encoding() {
+        let t1b1: T1B1 = "10-01-110-000001111".into();
+        let t1b1: T1B1 = t1b1.trits().take(15).collect();
+        let t3b1: T3B1 = t1b1.clone().into();
+        let t5b1: T5B1 = t1b1.clone().into();
+
+        assert_eq!("10-01-110-00000", string(t1b1.trits()));
+        assert_eq!("10-01-110-00000", string(t3b1.trits()));
+        assert_eq!("10-01-110-00000", string(t5b1.trits()));
+        assert_eq!("00000-011-10-01", string(t5b1.trits().rev()));
+        assert_eq!(15, t5b1.trits().len());
+    }
+
+    #[test]
+    fn trits_from_both_ends() {
+        let t3b1: T3B1 = "MN".into();
+        let mut trits = t3b1.trits();
+
+        assert_eq!(Some(BalancedTrit::PlusOne), trits.next());
+        assert_eq!(Some(BalancedTrit::MinusOne), trits.next_back());
+        assert_eq!(4, trits.len());
+        assert_eq!(Some(BalancedTrit::MinusOne), trits.nth(2));
+        assert_eq!(Some(BalancedTrit::MinusOne), trits.next());
+        assert_eq!(None, trits.next_back());
+        assert_eq!(None, trits.nth(5));
+    }
+
+    #[test]
+    fn trytes_with_padding() {
+        let t1b1: T1B1 = "1001-".into();
+
+        assert_eq!("AY", string(t1b1.trytes()));
+        assert_eq!("YA", string(t1b1.trytes().rev()));
+        assert_eq!(2, t1b1.trytes().len());
+
+        let t5b1: T5B1 = T1B1::from("111111111-").into();
+        assert_eq!("MMMZ", string(t5b1.trytes()));
+
+        let t3b1: T3B1 = "MINI9TRI".into();
+        assert_eq!("MINI9TRI", string(t3b1.trytes()));
+    }
+
+    #[test]
+    fn collect_into_encodings() {
+        let t3b1: T3B1 = "MINITRI9ABCDEFG".into();
+
+        let t5b1: T5B1 = t3b1.trits().collect();
+        let t1b1: T1B1 = t5b1.trits().collect();
+        let again: T3B1 = t1b1.trits().collect();
+        assert_eq!(t3b1, again);
+
+        let trytes: T3B1 = t5b1.trytes().rev().collect();
+        assert_eq!("GFEDCBA9IRTINIM", trytes.to_string());
+
+        // Incomplete groups are padded with zero trits.
+        let t5b1: T5B1 = "1-".chars().map(BalancedTrit::from).collect();
+        assert_eq!("1-000", string(t5b1.trits()));
+        let t3b1: T3B1 = "1-".chars().map(BalancedTrit::from).collect();
+        assert_eq!(vec![BalancedTryte::Y], t3b1.into_iter().collect::<Vec<_>>());
+    }
+
+    /// An encoding outside of this module, which only provides `TritAccess`.
+    struct Alternating(usize);
+
+    impl Encoding for Alternating {
+        fn new() -> Self {
+            Alternating(0)
+        }
+
+        fn with_capacity(_: usize) -> Self {
+            Alternating(0)
+        }
+
+        fn len(&self) -> usize {
+            self.0
+        }
+
+        fn add(&mut self, trits: T1B1) {
+            self.0 += trits.len();
+        }
+    }
+
+    impl TritAccess for Alternating {
+        fn trit_len(&self) -> usize {
+            self.0
+        }
+
+        fn trit(&self, index: usize) -> BalancedTrit {
+            if index & 1 == 0 {
+                BalancedTrit::PlusOne
+            } else {
+                BalancedTrit::MinusOne
+            }
+        }
+    }
+
+    #[test]
+    fn iterate_custom_encoding() {
+        let mut alternating = Alternating::new();
+        alternating.add("0000".into());
+
+        assert_eq!("1-1-", string(alternating.trits()));
+        assert_eq!("GZ", string(alternating.trytes()));
+        assert_eq!("-1-1", string(alternating.trits().rev()));
+    }
+}
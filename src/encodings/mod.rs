@@ -1,12 +1,241 @@
+pub mod iter;
+pub mod packed;
 pub mod t1b1;
+pub mod t1b2;
+pub mod t20b4;
 pub mod t3b1;
+pub mod t40b8;
 pub mod t5b1;
+pub mod t5b1_key;
+pub mod t5b1_signed;
+mod word;
 //mod t9b2;
 
-pub trait Encoding {
+use crate::error::Error;
+use crate::text;
+
+use iter::{TritAccess, Trits, Trytes};
+
+pub trait Encoding: TritAccess {
     fn new() -> Self;
     fn with_capacity(capacity: usize) -> Self;
     fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
     // TODO: someting that implements Into<Self>
     fn add(&mut self, trits: t1b1::T1B1); // use bytes
+
+    /// Iterates over the trits without decoding the whole sequence first.
+    fn trits(&self) -> Trits<'_>
+    where
+        Self: Sized,
+    {
+        Trits::new(self)
+    }
+
+    /// Iterates over groups of 3 trits, padding the last group with zeros.
+    fn trytes(&self) -> Trytes<'_>
+    where
+        Self: Sized,
+    {
+        Trytes::new(self)
+    }
+}
+
+/// Access to the bytes an encoding stores internally, e.g. for hex or base64 fixtures.
+pub trait RawBytes: Sized {
+    fn raw_bytes(&self) -> Vec<u8>;
+
+    /// Fails for bytes that the encoding never stores.
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, Error>;
+
+    fn to_hex(&self) -> String {
+        text::to_hex(&self.raw_bytes())
+    }
+
+    fn from_hex(s: &str) -> Result<Self, Error> {
+        Self::from_raw_bytes(&text::from_hex(s)?)
+    }
+
+    fn to_base64(&self) -> String {
+        text::to_base64(&self.raw_bytes())
+    }
+
+    fn from_base64(s: &str) -> Result<Self, Error> {
+        Self::from_raw_bytes(&text::from_base64(s)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::packed::Packed;
+    use super::{t1b1::T1B1, t1b2::T1B2, t20b4::T20B4, t3b1::T3B1, t40b8::T40B8};
+    use super::{t5b1::T5B1, t5b1_key::T5B1Key, t5b1_signed::T5B1Signed, Encoding};
+    use crate::random::TritRng;
+    use crate::trit::BalancedTrit;
+
+    use std::iter::FromIterator;
+
+    const ITERATIONS: usize = 256;
+
+    fn t5b1_bytes(t5b1: &T5B1) -> Vec<u8> {
+        (0..t5b1.len()).map(|i| t5b1.get(i)).collect()
+    }
+
+    /// Checks every conversion path that is available for the given trits.
+    fn assert_round_trips(trits: &T1B1) {
+        let expected = trits.to_string();
+
+        let t3b1: T3B1 = trits.clone().into();
+        assert_eq!(trits.len() / 3, t3b1.len());
+        let t1b1: T1B1 = t3b1.clone().into();
+        assert_eq!(expected, t1b1.to_string(), "T1B1 -> T3B1 -> T1B1");
+
+        let t5b1: T5B1 = trits.clone().into();
+        assert_eq!(trits.len() / 5, t5b1.len());
+        let t1b1: T1B1 = t5b1.clone().into();
+        assert_eq!(expected, t1b1.to_string(), "T1B1 -> T5B1 -> T1B1");
+
+        let t5b1_from_t3b1: T5B1 = t3b1.clone().into();
+        assert_eq!(
+            t5b1_bytes(&t5b1),
+            t5b1_bytes(&t5b1_from_t3b1),
+            "T3B1 -> T5B1"
+        );
+        let t1b1: T1B1 = t5b1_from_t3b1.into();
+        let t3b1_again: T3B1 = t1b1.into();
+        assert_eq!(
+            t3b1.to_string(),
+            t3b1_again.to_string(),
+            "T3B1 -> T5B1 -> T3B1"
+        );
+    }
+
+    #[test]
+    fn round_trip_random_trits() {
+        let mut rng = TritRng::seeded(0x6d69_6e69_7472_6921);
+
+        for i in 0..ITERATIONS {
+            // All conversions currently require a multiple of 15 trits.
+            assert_round_trips(&rng.t1b1(i % 40 * 15));
+        }
+    }
+
+    /// Collecting pads the last group with zeros, so converting back yields the trits followed
+    /// by up to one group of zeros.
+    fn assert_padded<T>(trits: &T1B1, group: usize)
+    where
+        T: Encoding + FromIterator<BalancedTrit>,
+    {
+        let encoded: T = trits.trits().collect();
+        let padded = trits.len().div_ceil(group) * group;
+        let expected = format!("{}{}", trits, "0".repeat(padded - trits.len()));
+
+        assert_eq!(padded, encoded.trit_len(), "{} trits", trits.len());
+        assert_eq!(
+            expected,
+            encoded.trits().map(|t| t.to_string()).collect::<String>()
+        );
+    }
+
+    #[test]
+    fn round_trip_padded_lengths() {
+        let mut rng = TritRng::seeded(0x7061_6464_696e_6721);
+
+        for i in 0..ITERATIONS {
+            let trits = rng.t1b1(i % 200);
+
+            assert_padded::<T3B1>(&trits, 3);
+            assert_padded::<T5B1>(&trits, 5);
+            assert_padded::<T1B2>(&trits, 1);
+            assert_padded::<T20B4>(&trits, 20);
+            assert_padded::<T40B8>(&trits, 40);
+            assert_padded::<Packed<7, 2>>(&trits, 7);
+            assert_padded::<T5B1Signed>(&trits, 5);
+            assert_padded::<T5B1Key>(&trits, 5);
+        }
+    }
+
+    #[test]
+    fn round_trip_empty() {
+        assert_round_trips(&T1B1::new());
+    }
+
+    #[test]
+    fn round_trip_uniform_trits() {
+        for trit in &[-1, 0, 1] {
+            assert_round_trips(&T1B1::from_i8(&[*trit; 240]));
+        }
+    }
+
+    #[test]
+    fn round_trip_all_t5b1_bytes() {
+        let bytes = (0..243).map(|b| b as u8).collect::<Vec<u8>>();
+
+        let t1b1: T1B1 = T5B1::from_u8(&bytes).into();
+        let t5b1: T5B1 = t1b1.into();
+
+        assert_eq!(bytes, t5b1_bytes(&t5b1));
+    }
+
+    #[test]
+    fn t5b1_lut_corners() {
+        let corners: [(u8, &str); 5] = [
+            (0, "00000"),
+            (1, "10000"),
+            (121, "11111"),
+            (122, "-----"),
+            (242, "-0000"),
+        ];
+
+        for (byte, trits) in corners.iter() {
+            let t1b1: T1B1 = T5B1::from_u8(&[*byte]).into();
+            assert_eq!(*trits, t1b1.to_string(), "byte {}", byte);
+
+            let t5b1: T5B1 = T1B1::from(*trits).into();
+            assert_eq!(vec![*byte], t5b1_bytes(&t5b1), "trits {}", trits);
+        }
+    }
+
+    #[test]
+    fn equality_across_encodings() {
+        let mut rng = TritRng::seeded(0x6571_7561_6c69_7479);
+
+        for i in 0..ITERATIONS {
+            let trits = rng.t1b1(i % 8 * 15);
+            let t3b1: T3B1 = trits.clone().into();
+            let t5b1: T5B1 = trits.clone().into();
+
+            assert!(trits == t3b1);
+            assert!(t3b1 == trits);
+            assert!(trits == t5b1);
+            assert!(t5b1 == trits);
+            assert!(t3b1 == t5b1);
+            assert!(t5b1 == t3b1);
+        }
+
+        let trits: T1B1 = "10-01-110000000".into();
+        assert!(trits != T3B1::from("MINI9"));
+        let shorter: T5B1 = T1B1::from("10-01-1100").into();
+        assert!(trits != shorter);
+        assert!(T1B1::from("00000") != T5B1::from_u8(&[243]));
+    }
+
+    #[test]
+    fn ordering_agrees_across_encodings() {
+        let mut rng = TritRng::seeded(0x6f72_6465_7269_6e67);
+        let mut samples = (0..ITERATIONS)
+            .map(|i| rng.t1b1(i % 4 * 15))
+            .collect::<Vec<T1B1>>();
+        samples.sort();
+
+        for pair in samples.windows(2) {
+            let t3b1 = pair.iter().cloned().map(T3B1::from).collect::<Vec<_>>();
+            let t5b1 = pair.iter().cloned().map(T5B1::from).collect::<Vec<_>>();
+
+            assert_eq!(pair[0].cmp(&pair[1]), t3b1[0].cmp(&t3b1[1]));
+            assert_eq!(pair[0].cmp(&pair[1]), t5b1[0].cmp(&t5b1[1]));
+        }
+    }
 }
@@ -0,0 +1,349 @@
+//! Encoding, where `TRITS` trits are stored in `BYTES` little endian bytes.
+//!
+//! Advantages:
+//!     * Any density that fits into 64 bits is a type alias away, e.g. `Packed<4, 1>`.
+//!     * Groups are stored offset by their smallest value, like `T20B4`, so a group read with
+//!       `get` compares like the number it holds.
+//! Disadvantages:
+//!     * Generic decoding, a hand-written encoding like T5B1 is faster for its density.
+//!     * The bytes of a group are little endian, so for `BYTES > 1` comparing bytes doesn't
+//!       compare values, see `T5B1Key` for that.
+
+use super::iter::TritAccess;
+use super::word::{word_trit, POW3};
+use super::{t1b1::T1B1, t5b1::T5B1, Encoding};
+use crate::error::Error;
+use crate::trit::BalancedTrit;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::FromIterator;
+
+/// 4 trits per byte.
+pub type T4B1 = Packed<4, 1>;
+/// 8 trits per 2 bytes.
+pub type T8B2 = Packed<8, 2>;
+/// 15 trits per 3 bytes, the same density as T5B1.
+pub type T15B3 = Packed<15, 3>;
+
+// NOTE: `Packed<5, 1>` has the density of `T5B1`, but not its bytes: it stores the value offset
+// by 121, while `T5B1` stores the value modulo 243. Convert with `From` and `TryFrom` instead.
+
+#[derive(Clone, Default, Eq, Hash, PartialEq)]
+pub struct Packed<const TRITS: usize, const BYTES: usize>(Vec<u8>);
+
+impl<const TRITS: usize, const BYTES: usize> Packed<TRITS, BYTES> {
+    /// Fails to compile for densities that don't fit, i.e. unless `3^TRITS <= 256^BYTES`.
+    const VALID: () = assert!(
+        TRITS > 0 && TRITS <= 40 && BYTES <= 8 && POW3[TRITS] as u128 <= 1u128 << (8 * BYTES),
+        "3^TRITS doesn't fit into BYTES bytes"
+    );
+
+    /// The largest valid group, i.e. all trits set to one.
+    pub const MAX_GROUP: u64 = POW3[TRITS] - 1;
+
+    /// Takes the bytes after checking that they form whole groups of valid values.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, Error> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+
+        if bytes.len() % BYTES != 0 {
+            return Err(Error::InvalidByteLength {
+                len: bytes.len(),
+                expected: bytes.len().next_multiple_of(BYTES),
+            });
+        }
+
+        match bytes
+            .chunks(BYTES)
+            .map(read_group)
+            .enumerate()
+            .find(|(_, group)| *group > Self::MAX_GROUP)
+        {
+            Some((offset, value)) => Err(Error::InvalidWord { offset, value }),
+            None => Ok(Self(bytes)),
+        }
+    }
+
+    /// The group at `index`, offset by its smallest value.
+    pub fn get(&self, index: usize) -> u64 {
+        read_group(&self.0[index * BYTES..(index + 1) * BYTES])
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Converts to another density, padding the last group with zero trits if necessary.
+    pub fn repack<const T: usize, const B: usize>(&self) -> Packed<T, B> {
+        self.trits().collect()
+    }
+
+    /// Packs up to `TRITS` trits, padding the group with zero trits.
+    fn push_group(&mut self, trits: &[BalancedTrit]) {
+        let group = (0..TRITS).rev().fold(0u64, |group, i| {
+            let trit = trits.get(i).map_or(0, |trit| *trit as i8);
+            group * 3 + (trit + 1) as u64
+        });
+
+        self.0.extend_from_slice(&group.to_le_bytes()[..BYTES]);
+    }
+}
+
+pub(crate) fn read_group(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0u64, |group, byte| group << 8 | *byte as u64)
+}
+
+impl<const TRITS: usize, const BYTES: usize> Encoding for Packed<TRITS, BYTES> {
+    fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID;
+
+        Self(Vec::with_capacity(capacity * BYTES))
+    }
+
+    fn add(&mut self, trits: T1B1) {
+        let packed: Self = trits.into();
+        self.0.extend(packed.0);
+    }
+
+    fn len(&self) -> usize {
+        self.0.len() / BYTES
+    }
+}
+
+impl<const TRITS: usize, const BYTES: usize> TritAccess for Packed<TRITS, BYTES> {
+    fn trit_len(&self) -> usize {
+        self.len() * TRITS
+    }
+
+    fn trit(&self, index: usize) -> BalancedTrit {
+        word_trit(self.get(index / TRITS), index % TRITS)
+    }
+}
+
+impl<const TRITS: usize, const BYTES: usize> Ord for Packed<TRITS, BYTES> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.trits().cmp(other.trits())
+    }
+}
+
+impl<const TRITS: usize, const BYTES: usize> PartialOrd for Packed<TRITS, BYTES> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const TRITS: usize, const BYTES: usize> fmt::Display for Packed<TRITS, BYTES> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for trit in self.trits() {
+            trit.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const TRITS: usize, const BYTES: usize> fmt::Debug for Packed<TRITS, BYTES> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct(&format!("Packed<{}, {}>", TRITS, BYTES))
+            .field("len", &self.len())
+            .field("trits", &(self.len() * TRITS))
+            .field("value", &self.to_string())
+            .finish()
+    }
+}
+
+/// Pads the last group with zero trits, if the number of trits is not a multiple of `TRITS`.
+impl<const TRITS: usize, const BYTES: usize> From<T1B1> for Packed<TRITS, BYTES> {
+    fn from(input: T1B1) -> Self {
+        let mut packed = Self::with_capacity(input.len().div_ceil(TRITS));
+        for group in input.as_slice().chunks(TRITS) {
+            packed.push_group(group);
+        }
+        packed
+    }
+}
+
+impl<const TRITS: usize, const BYTES: usize> From<Packed<TRITS, BYTES>> for T1B1 {
+    fn from(input: Packed<TRITS, BYTES>) -> T1B1 {
+        input.trits().collect()
+    }
+}
+
+/// Pads the last group with zero trits, like `From<T1B1>`. Fails for bytes above 242, see
+/// `T5B1::is_canonical`.
+impl<const TRITS: usize, const BYTES: usize> TryFrom<T5B1> for Packed<TRITS, BYTES> {
+    type Error = Error;
+
+    fn try_from(input: T5B1) -> Result<Self, Error> {
+        match input.as_bytes().iter().position(|b| *b > 242) {
+            Some(offset) => Err(Error::InvalidByte {
+                offset,
+                value: input.get(offset),
+            }),
+            None => Ok(input.trits().collect()),
+        }
+    }
+}
+
+/// Pads the last byte with zero trits, like `FromIterator` for `T5B1`.
+impl<const TRITS: usize, const BYTES: usize> From<Packed<TRITS, BYTES>> for T5B1 {
+    fn from(input: Packed<TRITS, BYTES>) -> T5B1 {
+        input.trits().collect()
+    }
+}
+
+/// Pads the last group with zero trits, if the number of trits is not a multiple of `TRITS`.
+impl<const TRITS: usize, const BYTES: usize> FromIterator<BalancedTrit> for Packed<TRITS, BYTES> {
+    fn from_iter<I: IntoIterator<Item = BalancedTrit>>(iter: I) -> Self {
+        let mut packed = Self::new();
+        let mut group = Vec::with_capacity(TRITS);
+
+        for trit in iter {
+            group.push(trit);
+            if group.len() == TRITS {
+                packed.push_group(&group);
+                group.clear();
+            }
+        }
+        if !group.is_empty() {
+            packed.push_group(&group);
+        }
+
+        packed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::t40b8::T40B8;
+
+    fn trits(n: usize) -> T1B1 {
+        let trits = (0..n)
+            .map(|i| ((i * 7 + i / 4) % 3) as i8 - 1)
+            .collect::<Vec<i8>>();
+        T1B1::from_i8(&trits)
+    }
+
+    #[test]
+    fn t1b1_round_trip() {
+        let input = trits(120);
+
+        let t4b1: T4B1 = input.clone().into();
+        assert_eq!(30, t4b1.len());
+        assert_eq!(30, t4b1.as_bytes().len());
+        assert_eq!(input, T1B1::from(t4b1));
+
+        let t8b2: T8B2 = input.clone().into();
+        assert_eq!(15, t8b2.len());
+        assert_eq!(30, t8b2.as_bytes().len());
+        assert_eq!(input, T1B1::from(t8b2));
+
+        let t15b3: T15B3 = input.clone().into();
+        assert_eq!(24, t15b3.as_bytes().len());
+        assert_eq!(input.to_string(), t15b3.to_string());
+    }
+
+    #[test]
+    fn t5b1_round_trip() {
+        let input: T5B1 = trits(120).into();
+
+        let t8b2 = T8B2::try_from(input.clone()).unwrap();
+        assert_eq!(input, T5B1::from(t8b2));
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 1,
+                value: 243
+            }),
+            T8B2::try_from(T5B1::from_u8(&[0, 243]))
+        );
+    }
+
+    #[test]
+    fn groups_match_word_encodings() {
+        let input = trits(80);
+        let words: T40B8 = input.clone().into();
+        let packed: Packed<40, 8> = input.into();
+
+        assert_eq!(words.as_words()[1], packed.get(1));
+        assert_eq!(words.to_string(), packed.to_string());
+    }
+
+    #[test]
+    fn repack_pads_last_group() {
+        let t4b1: T4B1 = "1-0-10".chars().map(BalancedTrit::from).collect();
+        assert_eq!("1-0-1000", t4b1.to_string());
+
+        let t8b2: T8B2 = t4b1.repack();
+        assert_eq!(1, t8b2.len());
+        assert_eq!("1-0-1000", t8b2.to_string());
+        assert_eq!("1-0-1000000", t8b2.repack::<11, 3>().to_string());
+    }
+
+    #[test]
+    fn pad_partial_groups() {
+        let t4b1: T4B1 = trits(7).into();
+        assert_eq!(2, t4b1.len());
+        assert_eq!(format!("{}0", trits(7)), t4b1.to_string());
+
+        let t5b1 = T5B1::from(t4b1.clone());
+        assert_eq!(2, t5b1.len());
+        assert_eq!(format!("{}000", trits(7)), format!("{:#}", t5b1));
+
+        let t8b2 = T8B2::try_from(t5b1).unwrap();
+        assert_eq!(format!("{}000000000", trits(7)), t8b2.to_string());
+    }
+
+    #[test]
+    fn debug_names_the_layout() {
+        let packed: Packed<5, 1> = trits(5).into();
+        assert!(format!("{:?}", packed).starts_with("Packed<5, 1> {"));
+    }
+
+    #[test]
+    fn validate_bytes() {
+        assert_eq!(
+            Ok(vec![80, 0]),
+            T4B1::from_bytes(vec![80, 0]).map(Packed::into_bytes)
+        );
+        assert_eq!(
+            Err(Error::InvalidWord {
+                offset: 1,
+                value: 81
+            }),
+            T4B1::from_bytes(vec![80, 81])
+        );
+        assert_eq!(
+            Err(Error::InvalidByteLength {
+                len: 3,
+                expected: 4
+            }),
+            T8B2::from_bytes(vec![0; 3])
+        );
+        assert_eq!(6560, T8B2::MAX_GROUP);
+    }
+
+    #[test]
+    fn order_by_trits() {
+        let a: T4B1 = T1B1::from("1-00").into();
+        let b: T4B1 = T1B1::from("1000").into();
+        let c: T4B1 = T1B1::from("-1111111").into();
+
+        assert!(a < b);
+        assert!(c < a);
+    }
+}
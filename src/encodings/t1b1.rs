@@ -6,16 +6,24 @@
 //! Disadvantages:
 //!     * Very memory-inefficient. (density: 1 trit per byte)
 
+use super::iter::TritAccess;
 use super::t3b1::T3B1;
-use super::t5b1::T5B1;
-use super::Encoding;
+use super::t5b1::{T5B1Ref, T5B1};
+use super::{Encoding, RawBytes};
 //use super::t9b2::T9B2;
 
+use crate::alphabet::{DisplayTrits, TritAlphabet};
+use crate::error::Error;
+use crate::simd;
+use crate::text;
 use crate::trit::BalancedTrit;
 
 use std::fmt;
+use std::iter::FromIterator;
 
-#[derive(Clone, Debug)]
+/// Sequences compare lexicographically by their trits, starting with the first one. For
+/// numeric ordering see `Numeric`.
+#[derive(Clone, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct T1B1(Vec<BalancedTrit>);
 
 impl T1B1 {
@@ -29,6 +37,25 @@ impl T1B1 {
         Self(trits)
     }
 
+    /// Like `From<&str>`, but with other characters and without panicking.
+    pub fn parse_with(s: &str, alphabet: &TritAlphabet) -> Result<Self, Error> {
+        let mut trits = Vec::with_capacity(s.len());
+
+        for (offset, c) in s.char_indices() {
+            match alphabet.from_char(c) {
+                Some(trit) => trits.push(trit),
+                None => return Err(Error::InvalidChar { offset, value: c }),
+            }
+        }
+
+        Ok(Self(trits))
+    }
+
+    /// Like `Display`, but with other characters.
+    pub fn display_with(&self, alphabet: &TritAlphabet) -> DisplayTrits<'_> {
+        DisplayTrits::new(self.trits(), alphabet)
+    }
+
     pub fn get(&self, index: usize) -> BalancedTrit {
         self.0[index]
     }
@@ -51,6 +78,10 @@ impl T1B1 {
     pub(crate) fn push_internal(&mut self, trit: BalancedTrit) {
         self.0.push(trit);
     }
+
+    pub(crate) fn as_slice(&self) -> &[BalancedTrit] {
+        &self.0
+    }
 }
 
 impl Encoding for T1B1 {
@@ -73,6 +104,16 @@ impl Encoding for T1B1 {
     }
 }
 
+impl TritAccess for T1B1 {
+    fn trit_len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn trit(&self, index: usize) -> BalancedTrit {
+        self.0[index]
+    }
+}
+
 impl<'a> From<&'a str> for T1B1 {
     fn from(s: &'a str) -> Self {
         let mut trits: Vec<BalancedTrit> = Vec::with_capacity(s.len());
@@ -94,36 +135,91 @@ impl fmt::Display for T1B1 {
     }
 }
 
+/// Shows the length, the number of trits and the trits, like the other encodings; `{:#?}`
+/// prints them on separate lines.
+impl fmt::Debug for T1B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("T1B1")
+            .field("len", &self.0.len())
+            .field("trits", &self.0.len())
+            .field("value", &self.to_string())
+            .finish()
+    }
+}
+
+impl fmt::LowerHex for T1B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::write_hex(f, &self.raw_bytes(), false)
+    }
+}
+
+impl fmt::UpperHex for T1B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::write_hex(f, &self.raw_bytes(), true)
+    }
+}
+
+/// One byte per trit: `0x00`, `0x01` or `0xff`.
+impl RawBytes for T1B1 {
+    fn raw_bytes(&self) -> Vec<u8> {
+        self.0.iter().map(|t| *t as i8 as u8).collect()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut trits = Vec::with_capacity(bytes.len());
+
+        for (offset, byte) in bytes.iter().enumerate() {
+            match *byte as i8 {
+                -1..=1 => trits.push((*byte as i8).into()),
+                _ => {
+                    return Err(Error::InvalidByte {
+                        offset,
+                        value: *byte,
+                    })
+                }
+            }
+        }
+
+        Ok(Self(trits))
+    }
+}
+
 impl From<T3B1> for T1B1 {
     fn from(input: T3B1) -> T1B1 {
-        let n = input.len();
-        let mut trits = Self::with_capacity(n * 3);
+        let trytes = input.as_slice();
+        let mut trits = vec![BalancedTrit::Zero; trytes.len() * 3];
+
+        let done = simd::t3b1_to_t1b1(trytes, &mut trits);
 
-        for tryte in input {
+        for (i, tryte) in trytes.iter().enumerate().skip(done) {
             let sub_trits = tryte.as_trits();
-            for trit in &sub_trits {
-                trits.push_internal((*trit).into());
+            for (j, trit) in sub_trits.iter().enumerate() {
+                trits[i * 3 + j] = (*trit).into();
             }
         }
 
-        trits
+        Self(trits)
     }
 }
 
 impl From<T5B1> for T1B1 {
     fn from(input: T5B1) -> T1B1 {
-        let n = input.len();
-        let mut trits = vec![0i8; n * 5];
+        input.borrowed().into()
+    }
+}
 
-        let mut j = 0;
+impl<'a> From<T5B1Ref<'a>> for T1B1 {
+    fn from(input: T5B1Ref<'a>) -> T1B1 {
+        let bytes = input.as_bytes();
+        let mut trits = vec![BalancedTrit::Zero; bytes.len() * 5];
 
-        (0..n).for_each(|i| {
-            trits[j..(j + 5)].copy_from_slice(&LUT[input.get(i) as usize]);
+        let done = simd::t5b1_to_t1b1(bytes, &mut trits);
 
-            j += 5;
-        });
+        for (i, byte) in bytes.iter().enumerate().skip(done) {
+            trits[i * 5..(i + 1) * 5].copy_from_slice(&decode_t5b1(*byte));
+        }
 
-        Self::from_i8(&trits)
+        Self(trits)
     }
 }
 
@@ -151,6 +247,12 @@ impl From<T9B2> for T1B1 {
 }
 */
 
+impl FromIterator<BalancedTrit> for T1B1 {
+    fn from_iter<I: IntoIterator<Item = BalancedTrit>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 impl IntoIterator for T1B1 {
     type Item = BalancedTrit;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -160,255 +262,66 @@ impl IntoIterator for T1B1 {
     }
 }
 
-const LUT: [[i8; 5]; 243] = [
-    [0, 0, 0, 0, 0],
-    [1, 0, 0, 0, 0],
-    [-1, 1, 0, 0, 0],
-    [0, 1, 0, 0, 0],
-    [1, 1, 0, 0, 0],
-    [-1, -1, 1, 0, 0],
-    [0, -1, 1, 0, 0],
-    [1, -1, 1, 0, 0],
-    [-1, 0, 1, 0, 0],
-    [0, 0, 1, 0, 0],
-    [1, 0, 1, 0, 0],
-    [-1, 1, 1, 0, 0],
-    [0, 1, 1, 0, 0],
-    [1, 1, 1, 0, 0],
-    [-1, -1, -1, 1, 0],
-    [0, -1, -1, 1, 0],
-    [1, -1, -1, 1, 0],
-    [-1, 0, -1, 1, 0],
-    [0, 0, -1, 1, 0],
-    [1, 0, -1, 1, 0],
-    [-1, 1, -1, 1, 0],
-    [0, 1, -1, 1, 0],
-    [1, 1, -1, 1, 0],
-    [-1, -1, 0, 1, 0],
-    [0, -1, 0, 1, 0],
-    [1, -1, 0, 1, 0],
-    [-1, 0, 0, 1, 0],
-    [0, 0, 0, 1, 0],
-    [1, 0, 0, 1, 0],
-    [-1, 1, 0, 1, 0],
-    [0, 1, 0, 1, 0],
-    [1, 1, 0, 1, 0],
-    [-1, -1, 1, 1, 0],
-    [0, -1, 1, 1, 0],
-    [1, -1, 1, 1, 0],
-    [-1, 0, 1, 1, 0],
-    [0, 0, 1, 1, 0],
-    [1, 0, 1, 1, 0],
-    [-1, 1, 1, 1, 0],
-    [0, 1, 1, 1, 0],
-    [1, 1, 1, 1, 0],
-    [-1, -1, -1, -1, 1],
-    [0, -1, -1, -1, 1],
-    [1, -1, -1, -1, 1],
-    [-1, 0, -1, -1, 1],
-    [0, 0, -1, -1, 1],
-    [1, 0, -1, -1, 1],
-    [-1, 1, -1, -1, 1],
-    [0, 1, -1, -1, 1],
-    [1, 1, -1, -1, 1],
-    [-1, -1, 0, -1, 1],
-    [0, -1, 0, -1, 1],
-    [1, -1, 0, -1, 1],
-    [-1, 0, 0, -1, 1],
-    [0, 0, 0, -1, 1],
-    [1, 0, 0, -1, 1],
-    [-1, 1, 0, -1, 1],
-    [0, 1, 0, -1, 1],
-    [1, 1, 0, -1, 1],
-    [-1, -1, 1, -1, 1],
-    [0, -1, 1, -1, 1],
-    [1, -1, 1, -1, 1],
-    [-1, 0, 1, -1, 1],
-    [0, 0, 1, -1, 1],
-    [1, 0, 1, -1, 1],
-    [-1, 1, 1, -1, 1],
-    [0, 1, 1, -1, 1],
-    [1, 1, 1, -1, 1],
-    [-1, -1, -1, 0, 1],
-    [0, -1, -1, 0, 1],
-    [1, -1, -1, 0, 1],
-    [-1, 0, -1, 0, 1],
-    [0, 0, -1, 0, 1],
-    [1, 0, -1, 0, 1],
-    [-1, 1, -1, 0, 1],
-    [0, 1, -1, 0, 1],
-    [1, 1, -1, 0, 1],
-    [-1, -1, 0, 0, 1],
-    [0, -1, 0, 0, 1],
-    [1, -1, 0, 0, 1],
-    [-1, 0, 0, 0, 1],
-    [0, 0, 0, 0, 1],
-    [1, 0, 0, 0, 1],
-    [-1, 1, 0, 0, 1],
-    [0, 1, 0, 0, 1],
-    [1, 1, 0, 0, 1],
-    [-1, -1, 1, 0, 1],
-    [0, -1, 1, 0, 1],
-    [1, -1, 1, 0, 1],
-    [-1, 0, 1, 0, 1],
-    [0, 0, 1, 0, 1],
-    [1, 0, 1, 0, 1],
-    [-1, 1, 1, 0, 1],
-    [0, 1, 1, 0, 1],
-    [1, 1, 1, 0, 1],
-    [-1, -1, -1, 1, 1],
-    [0, -1, -1, 1, 1],
-    [1, -1, -1, 1, 1],
-    [-1, 0, -1, 1, 1],
-    [0, 0, -1, 1, 1],
-    [1, 0, -1, 1, 1],
-    [-1, 1, -1, 1, 1],
-    [0, 1, -1, 1, 1],
-    [1, 1, -1, 1, 1],
-    [-1, -1, 0, 1, 1],
-    [0, -1, 0, 1, 1],
-    [1, -1, 0, 1, 1],
-    [-1, 0, 0, 1, 1],
-    [0, 0, 0, 1, 1],
-    [1, 0, 0, 1, 1],
-    [-1, 1, 0, 1, 1],
-    [0, 1, 0, 1, 1],
-    [1, 1, 0, 1, 1],
-    [-1, -1, 1, 1, 1],
-    [0, -1, 1, 1, 1],
-    [1, -1, 1, 1, 1],
-    [-1, 0, 1, 1, 1],
-    [0, 0, 1, 1, 1],
-    [1, 0, 1, 1, 1],
-    [-1, 1, 1, 1, 1],
-    [0, 1, 1, 1, 1],
-    [1, 1, 1, 1, 1],
-    [-1, -1, -1, -1, -1],
-    [0, -1, -1, -1, -1],
-    [1, -1, -1, -1, -1],
-    [-1, 0, -1, -1, -1],
-    [0, 0, -1, -1, -1],
-    [1, 0, -1, -1, -1],
-    [-1, 1, -1, -1, -1],
-    [0, 1, -1, -1, -1],
-    [1, 1, -1, -1, -1],
-    [-1, -1, 0, -1, -1],
-    [0, -1, 0, -1, -1],
-    [1, -1, 0, -1, -1],
-    [-1, 0, 0, -1, -1],
-    [0, 0, 0, -1, -1],
-    [1, 0, 0, -1, -1],
-    [-1, 1, 0, -1, -1],
-    [0, 1, 0, -1, -1],
-    [1, 1, 0, -1, -1],
-    [-1, -1, 1, -1, -1],
-    [0, -1, 1, -1, -1],
-    [1, -1, 1, -1, -1],
-    [-1, 0, 1, -1, -1],
-    [0, 0, 1, -1, -1],
-    [1, 0, 1, -1, -1],
-    [-1, 1, 1, -1, -1],
-    [0, 1, 1, -1, -1],
-    [1, 1, 1, -1, -1],
-    [-1, -1, -1, 0, -1],
-    [0, -1, -1, 0, -1],
-    [1, -1, -1, 0, -1],
-    [-1, 0, -1, 0, -1],
-    [0, 0, -1, 0, -1],
-    [1, 0, -1, 0, -1],
-    [-1, 1, -1, 0, -1],
-    [0, 1, -1, 0, -1],
-    [1, 1, -1, 0, -1],
-    [-1, -1, 0, 0, -1],
-    [0, -1, 0, 0, -1],
-    [1, -1, 0, 0, -1],
-    [-1, 0, 0, 0, -1],
-    [0, 0, 0, 0, -1],
-    [1, 0, 0, 0, -1],
-    [-1, 1, 0, 0, -1],
-    [0, 1, 0, 0, -1],
-    [1, 1, 0, 0, -1],
-    [-1, -1, 1, 0, -1],
-    [0, -1, 1, 0, -1],
-    [1, -1, 1, 0, -1],
-    [-1, 0, 1, 0, -1],
-    [0, 0, 1, 0, -1],
-    [1, 0, 1, 0, -1],
-    [-1, 1, 1, 0, -1],
-    [0, 1, 1, 0, -1],
-    [1, 1, 1, 0, -1],
-    [-1, -1, -1, 1, -1],
-    [0, -1, -1, 1, -1],
-    [1, -1, -1, 1, -1],
-    [-1, 0, -1, 1, -1],
-    [0, 0, -1, 1, -1],
-    [1, 0, -1, 1, -1],
-    [-1, 1, -1, 1, -1],
-    [0, 1, -1, 1, -1],
-    [1, 1, -1, 1, -1],
-    [-1, -1, 0, 1, -1],
-    [0, -1, 0, 1, -1],
-    [1, -1, 0, 1, -1],
-    [-1, 0, 0, 1, -1],
-    [0, 0, 0, 1, -1],
-    [1, 0, 0, 1, -1],
-    [-1, 1, 0, 1, -1],
-    [0, 1, 0, 1, -1],
-    [1, 1, 0, 1, -1],
-    [-1, -1, 1, 1, -1],
-    [0, -1, 1, 1, -1],
-    [1, -1, 1, 1, -1],
-    [-1, 0, 1, 1, -1],
-    [0, 0, 1, 1, -1],
-    [1, 0, 1, 1, -1],
-    [-1, 1, 1, 1, -1],
-    [0, 1, 1, 1, -1],
-    [1, 1, 1, 1, -1],
-    [-1, -1, -1, -1, 0],
-    [0, -1, -1, -1, 0],
-    [1, -1, -1, -1, 0],
-    [-1, 0, -1, -1, 0],
-    [0, 0, -1, -1, 0],
-    [1, 0, -1, -1, 0],
-    [-1, 1, -1, -1, 0],
-    [0, 1, -1, -1, 0],
-    [1, 1, -1, -1, 0],
-    [-1, -1, 0, -1, 0],
-    [0, -1, 0, -1, 0],
-    [1, -1, 0, -1, 0],
-    [-1, 0, 0, -1, 0],
-    [0, 0, 0, -1, 0],
-    [1, 0, 0, -1, 0],
-    [-1, 1, 0, -1, 0],
-    [0, 1, 0, -1, 0],
-    [1, 1, 0, -1, 0],
-    [-1, -1, 1, -1, 0],
-    [0, -1, 1, -1, 0],
-    [1, -1, 1, -1, 0],
-    [-1, 0, 1, -1, 0],
-    [0, 0, 1, -1, 0],
-    [1, 0, 1, -1, 0],
-    [-1, 1, 1, -1, 0],
-    [0, 1, 1, -1, 0],
-    [1, 1, 1, -1, 0],
-    [-1, -1, -1, 0, 0],
-    [0, -1, -1, 0, 0],
-    [1, -1, -1, 0, 0],
-    [-1, 0, -1, 0, 0],
-    [0, 0, -1, 0, 0],
-    [1, 0, -1, 0, 0],
-    [-1, 1, -1, 0, 0],
-    [0, 1, -1, 0, 0],
-    [1, 1, -1, 0, 0],
-    [-1, -1, 0, 0, 0],
-    [0, -1, 0, 0, 0],
-    [1, -1, 0, 0, 0],
-    [-1, 0, 0, 0, 0],
-];
+/// Decodes a single T5B1 byte by looking up its trits in a precomputed table. Bulk conversions
+/// only use this for the bytes the SIMD kernels leave over.
+#[cfg(not(feature = "t5b1-arith-decode"))]
+#[inline]
+pub(crate) fn decode_t5b1(byte: u8) -> [BalancedTrit; 5] {
+    match LUT.get(byte as usize) {
+        Some(trits) => *trits,
+        None => panic!("Invalid T5B1 byte"),
+    }
+}
+
+/// Decodes a single T5B1 byte arithmetically, which trades a few divisions for not having to
+/// keep a 1215 byte table in cache.
+#[cfg(feature = "t5b1-arith-decode")]
+#[inline]
+pub(crate) fn decode_t5b1(byte: u8) -> [BalancedTrit; 5] {
+    if byte > 242 {
+        panic!("Invalid T5B1 byte");
+    }
+    decode_t5b1_arith(byte)
+}
+
+const fn decode_t5b1_arith(byte: u8) -> [BalancedTrit; 5] {
+    // Adding 121 (`11111`) shifts every balanced trit into 0..=2, which turns the byte into
+    // plain unbalanced base 3.
+    let mut value = (byte as u16 + 121) % 243;
+    let mut trits = [BalancedTrit::Zero; 5];
+    let mut i = 0;
+
+    while i < 5 {
+        trits[i] = match value % 3 {
+            0 => BalancedTrit::MinusOne,
+            1 => BalancedTrit::Zero,
+            _ => BalancedTrit::PlusOne,
+        };
+        value /= 3;
+        i += 1;
+    }
+
+    trits
+}
+
+/// Maps every valid T5B1 byte (0..=242) to its five trits, least significant trit first.
+#[cfg(not(feature = "t5b1-arith-decode"))]
+const LUT: [[BalancedTrit; 5]; 243] = {
+    let mut lut = [[BalancedTrit::Zero; 5]; 243];
+    let mut byte = 0;
+
+    while byte < 243 {
+        lut[byte] = decode_t5b1_arith(byte as u8);
+        byte += 1;
+    }
+
+    lut
+};
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encodings::t1b2::T1B2;
     use crate::encodings::t3b1::T3B1;
 
     #[test]
@@ -456,4 +369,77 @@ mod tests {
 
         assert_eq!(24, trits.len());
     }
+
+    #[test]
+    fn parse_and_display_with_alphabet() {
+        let trits = T1B1::parse_with("↑0↓↓", &TritAlphabet::ARROWS).unwrap();
+
+        assert_eq!("10--", trits.to_string());
+        assert_eq!("+0--", trits.display_with(&TritAlphabet::SIGNS).to_string());
+        assert_eq!("10TT", trits.display_with(&TritAlphabet::T01).to_string());
+        assert_eq!(
+            Err(Error::InvalidChar {
+                offset: 4,
+                value: '-'
+            }),
+            T1B1::parse_with("↑0-", &TritAlphabet::ARROWS).map(|t| t.len())
+        );
+    }
+
+    #[test]
+    fn debug_shows_length_and_encoding() {
+        let trits: T1B1 = "10-".into();
+
+        assert_eq!(
+            "T1B1 { len: 3, trits: 3, value: \"10-\" }",
+            format!("{:?}", trits)
+        );
+        assert_eq!(
+            "T1B1 {\n    len: 3,\n    trits: 3,\n    value: \"10-\",\n}",
+            format!("{:#?}", trits)
+        );
+
+        let bytes: T5B1 = T1B1::from("10000").into();
+        assert_eq!(
+            "T5B1 { len: 1, trits: 5, bytes: \"01\", value: \"10000\" }",
+            format!("{:?}", bytes)
+        );
+        assert_eq!(
+            "T5B1 { len: 1, trits: 5, bytes: \"f3\" }",
+            format!("{:?}", T5B1::from_u8(&[243]))
+        );
+
+        let trytes: T3B1 = "MI".into();
+        assert_eq!(
+            "T3B1 { len: 2, trits: 6, value: \"MI\" }",
+            format!("{:?}", trytes)
+        );
+        let trits: T1B2 = T1B1::from("10-").into();
+        assert_eq!(
+            "T1B2 { len: 3, trits: 3, value: \"10-\" }",
+            format!("{:?}", trits)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_for_invalid_t5b1_byte() {
+        let _: T1B1 = T5B1::from_u8(&[243]).into();
+    }
+
+    #[test]
+    fn raw_bytes_hex() {
+        let trits: T1B1 = "10-".into();
+
+        assert_eq!("0100ff", trits.to_hex());
+        assert_eq!("0100FF", format!("{:X}", trits));
+        assert_eq!("10-", T1B1::from_hex("0100ff").unwrap().to_string());
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 2,
+                value: 2
+            }),
+            T1B1::from_hex("010002").map(|t| t.to_string())
+        );
+    }
 }
@@ -0,0 +1,9 @@
+//! Encoding, where 20 trits are stored in a single `u32`.
+//!
+//! Advantages:
+//!     * Same density as T5B1, but a quarter of the words to touch. (3^20 < 2^32)
+//!     * Numeric comparison and increment work on whole words.
+//! Disadvantages:
+//!     * Only multiples of 20 trits can be stored without padding.
+
+super::word::word_encoding!(T20B4, u32, 20, 4);
@@ -6,14 +6,22 @@
 //! Disadvantages:
 //!     * Still relatively memory-inefficient. (density: 3 trits per byte)
 
+use super::iter::TritAccess;
 use super::t1b1::T1B1;
-use super::Encoding;
+use super::{Encoding, RawBytes};
 
+use crate::alphabet::{DisplayTrytes, TryteAlphabet};
+use crate::error::Error;
+use crate::simd;
+use crate::text;
+use crate::trit::BalancedTrit;
 use crate::tryte::BalancedTryte;
 
+use std::cmp::Ordering;
 use std::fmt;
+use std::iter::FromIterator;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Default, Eq, Hash, PartialEq)]
 pub struct T3B1(Vec<BalancedTryte>);
 
 impl T3B1 {
@@ -27,6 +35,25 @@ impl T3B1 {
         Self(trytes)
     }
 
+    /// Like `From<&str>`, but with other characters and without panicking.
+    pub fn parse_with(s: &str, alphabet: &TryteAlphabet) -> Result<Self, Error> {
+        let mut trytes = Vec::with_capacity(s.len());
+
+        for (offset, c) in s.char_indices() {
+            match alphabet.from_char(c) {
+                Some(tryte) => trytes.push(tryte),
+                None => return Err(Error::InvalidChar { offset, value: c }),
+            }
+        }
+
+        Ok(Self(trytes))
+    }
+
+    /// Like `Display`, but with other characters.
+    pub fn display_with(&self, alphabet: &TryteAlphabet) -> DisplayTrytes<'_> {
+        DisplayTrytes::new(self.trytes(), alphabet)
+    }
+
     pub fn get(&self, index: usize) -> BalancedTryte {
         self.0[index]
     }
@@ -49,6 +76,82 @@ impl T3B1 {
     pub(crate) fn push_internal(&mut self, tryte: BalancedTryte) {
         self.0.push(tryte);
     }
+
+    pub(crate) fn as_slice(&self) -> &[BalancedTryte] {
+        &self.0
+    }
+
+    /// Encodes every byte as two trytes, `byte % 27` followed by `byte / 27`, each taken as an
+    /// index into the tryte alphabet `9AB...Z`.
+    pub fn from_bytes_ascii(bytes: &[u8]) -> Self {
+        let mut trytes = Vec::with_capacity(bytes.len() * 2);
+
+        for byte in bytes {
+            trytes.push(tryte_from_index(byte % 27));
+            trytes.push(tryte_from_index(byte / 27));
+        }
+
+        Self(trytes)
+    }
+
+    /// Decodes two trytes per byte. A single trailing `9` is treated as padding.
+    pub fn to_bytes_ascii(&self) -> Result<Vec<u8>, Error> {
+        let mut trytes = &self.0[..];
+
+        if trytes.len() % 2 == 1 {
+            match trytes.last() {
+                Some(BalancedTryte::Nine) => trytes = &trytes[..trytes.len() - 1],
+                _ => return Err(Error::OddTryteLength { len: trytes.len() }),
+            }
+        }
+
+        let mut bytes = Vec::with_capacity(trytes.len() / 2);
+
+        for (i, pair) in trytes.chunks(2).enumerate() {
+            let value = index_from_tryte(pair[0]) as u16 + index_from_tryte(pair[1]) as u16 * 27;
+            if value > 255 {
+                return Err(Error::InvalidBytePair {
+                    offset: i * 2,
+                    value,
+                });
+            }
+            bytes.push(value as u8);
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn from_str_utf8(s: &str) -> Self {
+        Self::from_bytes_ascii(s.as_bytes())
+    }
+
+    /// Decodes the trytes as UTF-8 text, like `to_bytes_ascii`. Only a single trailing `9` is
+    /// padding, `99` pairs are NUL characters and kept.
+    pub fn to_string_utf8(&self) -> Result<String, Error> {
+        let bytes = self.to_bytes_ascii()?;
+
+        String::from_utf8(bytes).map_err(|e| Error::InvalidUtf8 {
+            offset: e.utf8_error().valid_up_to(),
+        })
+    }
+}
+
+fn tryte_from_index(index: u8) -> BalancedTryte {
+    let index = index as i8;
+    if index > 13 {
+        (index - 27).into()
+    } else {
+        index.into()
+    }
+}
+
+fn index_from_tryte(tryte: BalancedTryte) -> u8 {
+    let value = tryte as i8;
+    if value < 0 {
+        (value + 27) as u8
+    } else {
+        value as u8
+    }
 }
 
 impl Encoding for T3B1 {
@@ -73,11 +176,63 @@ impl Encoding for T3B1 {
     }
 }
 
+impl TritAccess for T3B1 {
+    fn trit_len(&self) -> usize {
+        self.0.len() * 3
+    }
+
+    fn trit(&self, index: usize) -> BalancedTrit {
+        self.0[index / 3].to_trits()[index % 3]
+    }
+
+    fn tryte(&self, index: usize) -> BalancedTryte {
+        self.0[index]
+    }
+}
+
+/// Compares lexicographically by trits like `T1B1`, and not by tryte values, so that sorting
+/// doesn't depend on the encoding.
+impl Ord for T3B1 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let trits = |t: &BalancedTryte| t.to_trits();
+        self.0.iter().map(trits).cmp(other.0.iter().map(trits))
+    }
+}
+
+impl PartialOrd for T3B1 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<T1B1> for T3B1 {
+    fn eq(&self, other: &T1B1) -> bool {
+        self.0.len() * 3 == other.len()
+            && self
+                .0
+                .iter()
+                .zip(other.as_slice().chunks(3))
+                .all(|(tryte, trits)| tryte.to_trits() == trits)
+    }
+}
+
+impl PartialEq<T3B1> for T1B1 {
+    fn eq(&self, other: &T3B1) -> bool {
+        other == self
+    }
+}
+
 impl<'a> From<&'a str> for T3B1 {
     fn from(s: &'a str) -> Self {
+        let bytes = s.as_bytes();
+        let valid = simd::valid_tryte_prefix(bytes);
+
         let mut trytes = Vec::with_capacity(s.len());
 
-        for c in s.chars() {
+        for b in &bytes[..valid] {
+            trytes.push((*b as char).into());
+        }
+        for c in s[valid..].chars() {
             trytes.push(c.into());
         }
 
@@ -94,26 +249,110 @@ impl fmt::Display for T3B1 {
     }
 }
 
+/// Shows the number of trytes and trits, and the trytes as the value.
+impl fmt::Debug for T3B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("T3B1")
+            .field("len", &self.0.len())
+            .field("trits", &(self.0.len() * 3))
+            .field("value", &self.to_string())
+            .finish()
+    }
+}
+
+impl fmt::LowerHex for T3B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::write_hex(f, &self.raw_bytes(), false)
+    }
+}
+
+impl fmt::UpperHex for T3B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::write_hex(f, &self.raw_bytes(), true)
+    }
+}
+
+/// One byte per tryte, holding its value in -13..=13 as `i8`.
+impl RawBytes for T3B1 {
+    fn raw_bytes(&self) -> Vec<u8> {
+        self.0.iter().map(|t| *t as i8 as u8).collect()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut trytes = Vec::with_capacity(bytes.len());
+
+        for (offset, byte) in bytes.iter().enumerate() {
+            match *byte as i8 {
+                -13..=13 => trytes.push((*byte as i8).into()),
+                _ => {
+                    return Err(Error::InvalidByte {
+                        offset,
+                        value: *byte,
+                    })
+                }
+            }
+        }
+
+        Ok(Self(trytes))
+    }
+}
+
 impl From<T1B1> for T3B1 {
+    // The `+ 0` offsets keep the three trits aligned.
+    #[allow(clippy::identity_op, clippy::manual_is_multiple_of)]
     fn from(input: T1B1) -> T3B1 {
         let n = input.len();
         if n % 3 != 0 {
             unimplemented!("handle not-mulitple-of-3 case");
         }
 
-        let mut trytes = T3B1::with_capacity(n / 3);
+        let trits = input.as_slice();
+        let mut trytes = vec![BalancedTryte::Nine; n / 3];
 
-        (0..n).step_by(3).for_each(|i| {
-            let a = input.get(i + 0) as i8;
-            let b = input.get(i + 1) as i8;
-            let c = input.get(i + 2) as i8;
+        let done = simd::t1b1_to_t3b1(trits, &mut trytes);
+
+        (done..n / 3).for_each(|j| {
+            let i = j * 3;
+            let a = trits[i + 0] as i8;
+            let b = trits[i + 1] as i8;
+            let c = trits[i + 2] as i8;
 
             let v = a + b * 3 + c * 9;
 
-            trytes.push_internal(v.into());
+            trytes[j] = v.into();
         });
 
-        trytes
+        Self(trytes)
+    }
+}
+
+/// Pads the last tryte with zero trits, if the number of trits is not a multiple of 3.
+impl FromIterator<BalancedTrit> for T3B1 {
+    fn from_iter<I: IntoIterator<Item = BalancedTrit>>(iter: I) -> Self {
+        let mut trytes = Vec::new();
+        let mut group = [BalancedTrit::Zero; 3];
+        let mut n = 0;
+
+        for trit in iter {
+            group[n] = trit;
+            n += 1;
+            if n == 3 {
+                trytes.push(BalancedTryte::from_trits(group));
+                n = 0;
+            }
+        }
+        if n > 0 {
+            group[n..].fill(BalancedTrit::Zero);
+            trytes.push(BalancedTryte::from_trits(group));
+        }
+
+        Self(trytes)
+    }
+}
+
+impl FromIterator<BalancedTryte> for T3B1 {
+    fn from_iter<I: IntoIterator<Item = BalancedTryte>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
     }
 }
 
@@ -148,6 +387,25 @@ mod tests {
         assert_eq!(8, trytes.len());
     }
 
+    #[test]
+    fn parse_and_display_with_alphabet() {
+        let trytes = T3B1::parse_with("mini0tri", &TryteAlphabet::LOWER);
+        assert_eq!(
+            Err(Error::InvalidChar {
+                offset: 4,
+                value: '0'
+            }),
+            trytes.map(|t| t.len())
+        );
+
+        let trytes = T3B1::parse_with("MINI0TRI", &TryteAlphabet::ZERO).unwrap();
+        assert_eq!("MINI9TRI", trytes.to_string());
+        assert_eq!(
+            "mini9tri",
+            trytes.display_with(&TryteAlphabet::LOWER).to_string()
+        );
+    }
+
     #[test]
     fn push_and_pop() {
         let mut trytes = T3B1::new();
@@ -167,4 +425,57 @@ mod tests {
 
         assert_eq!("MINI9TRI", trytes.to_string());
     }
+
+    #[test]
+    fn bytes_ascii_round_trip() {
+        let bytes = (0..=255).collect::<Vec<u8>>();
+        let trytes = T3B1::from_bytes_ascii(&bytes);
+
+        assert_eq!(512, trytes.len());
+        assert_eq!(Ok(bytes), trytes.to_bytes_ascii());
+    }
+
+    #[test]
+    fn bytes_ascii_known_values() {
+        let trytes = T3B1::from_bytes_ascii(b"IOTA");
+
+        assert_eq!("SBYBCCKB", trytes.to_string());
+        assert_eq!(
+            Ok(b"IOTA".to_vec()),
+            T3B1::from("SBYBCCKB9").to_bytes_ascii()
+        );
+    }
+
+    #[test]
+    fn bytes_ascii_errors() {
+        assert_eq!(
+            Err(Error::OddTryteLength { len: 3 }),
+            T3B1::from("SBA").to_bytes_ascii()
+        );
+        assert_eq!(
+            Err(Error::InvalidBytePair {
+                offset: 2,
+                value: 728
+            }),
+            T3B1::from("SBZZ").to_bytes_ascii()
+        );
+    }
+
+    #[test]
+    fn string_utf8_round_trip() {
+        let trytes = T3B1::from_str_utf8("Grüße, minitri");
+        let mut padded = trytes.clone();
+        padded.push('9');
+
+        assert_eq!(Ok("Grüße, minitri".to_string()), trytes.to_string_utf8());
+        assert_eq!(Ok("Grüße, minitri".to_string()), padded.to_string_utf8());
+        assert_eq!(
+            Ok("a\0".to_string()),
+            T3B1::from_str_utf8("a\0").to_string_utf8()
+        );
+        assert_eq!(
+            Err(Error::InvalidUtf8 { offset: 0 }),
+            T3B1::from_bytes_ascii(&[0xff]).to_string_utf8()
+        );
+    }
 }
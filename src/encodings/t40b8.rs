@@ -0,0 +1,9 @@
+//! Encoding, where 40 trits are stored in a single `u64`.
+//!
+//! Advantages:
+//!     * Same density as T5B1, but an eighth of the words to touch. (3^40 < 2^64)
+//!     * Numeric comparison and increment work on whole words.
+//! Disadvantages:
+//!     * Only multiples of 40 trits can be stored without padding.
+
+super::word::word_encoding!(T40B8, u64, 40, 8);
@@ -1,6 +0,0 @@
-//! Encoding, where 4 trits are stored in a single byte.
-//!
-//! Advantages:
-//!     * Better memory-efficiency than T3B1.
-//! Disadvantages:
-//!     * Doesn't excel in any particular category, which reduces its usecases (see T8B1 in comparison).
@@ -1,16 +1,259 @@
-use crate::encodings::{t1b1::T1B1, t3b1::T3B1, Encoding};
+use crate::encodings::iter::{TritAccess, Trits, Trytes};
+use crate::encodings::t1b1::{decode_t5b1, T1B1};
+use crate::encodings::{t3b1::T3B1, Encoding, RawBytes};
+use crate::error::Error;
+use crate::simd;
+use crate::text;
+use crate::trit::BalancedTrit;
 
-#[derive(Clone, Debug)]
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::FromIterator;
+
+#[derive(Clone, Default, Eq, Hash, PartialEq)]
 pub struct T5B1(Vec<u8>);
 
 impl T5B1 {
+    /// Copies the bytes without checking them, see `from_bytes` and `is_canonical`.
     pub fn from_u8(input: &[u8]) -> Self {
         Self(Vec::from(input))
     }
 
+    /// Copies the bytes, after checking that each of them is in 0..=242.
+    pub fn from_bytes(input: &[u8]) -> Result<Self, Error> {
+        validate(input)?;
+        Ok(Self(Vec::from(input)))
+    }
+
+    /// Takes ownership of the bytes without copying, after checking that each of them is in
+    /// 0..=242.
+    pub fn from_vec(input: Vec<u8>) -> Result<Self, Error> {
+        validate(&input)?;
+        Ok(Self(input))
+    }
+
+    /// Packs the trits into the front of `buffer` and returns the number of bytes written.
+    ///
+    /// NOTE: the number of trits must be a multiple of 5.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn encode_into(trits: &T1B1, buffer: &mut [u8]) -> Result<usize, Error> {
+        if trits.len() % 5 != 0 {
+            return Err(Error::InvalidTritLength {
+                len: trits.len(),
+                multiple: 5,
+            });
+        }
+
+        let n = trits.len() / 5;
+        if buffer.len() < n {
+            return Err(Error::BufferTooSmall {
+                len: buffer.len(),
+                required: n,
+            });
+        }
+
+        encode(trits.as_slice(), &mut buffer[..n]);
+        Ok(n)
+    }
+
     pub fn get(&self, index: usize) -> u8 {
         self.0[index]
     }
+
+    /// Returns whether every byte is in 0..=242.
+    pub fn is_canonical(&self) -> bool {
+        validate(&self.0).is_ok()
+    }
+
+    /// Decodes exactly `len` trits. Unlike the `From` conversion this never panics; it fails for
+    /// invalid bytes, for a byte count other than `len` rounded up to groups of 5, and for
+    /// padding trits in the last group that aren't zero.
+    pub fn decode_strict(&self, len: usize) -> Result<T1B1, Error> {
+        self.borrowed().decode_strict(len)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Borrows the bytes without checking them, like `from_u8`.
+    pub fn borrowed(&self) -> T5B1Ref<'_> {
+        T5B1Ref(&self.0)
+    }
+}
+
+/// T5B1 bytes borrowed from somewhere else, e.g. a network buffer or a memory mapped file.
+#[derive(Clone, Copy, Debug)]
+pub struct T5B1Ref<'a>(&'a [u8]);
+
+impl<'a> T5B1Ref<'a> {
+    /// Checks that each byte is in 0..=242.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, Error> {
+        validate(bytes)?;
+        Ok(Self(bytes))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> u8 {
+        self.0[index]
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    pub fn to_t5b1(&self) -> T5B1 {
+        T5B1(Vec::from(self.0))
+    }
+
+    pub fn trits(&self) -> Trits<'_> {
+        Trits::new(self)
+    }
+
+    pub fn trytes(&self) -> Trytes<'_> {
+        Trytes::new(self)
+    }
+
+    /// See `T5B1::decode_strict`. Checks the bytes again, since `T5B1::borrowed` doesn't.
+    pub fn decode_strict(&self, len: usize) -> Result<T1B1, Error> {
+        validate(self.0)?;
+
+        let expected = len.div_ceil(5);
+        if self.0.len() != expected {
+            return Err(Error::InvalidByteLength {
+                len: self.0.len(),
+                expected,
+            });
+        }
+
+        let mut trits: T1B1 = (*self).into();
+        if let Some(i) = (len..trits.len()).find(|i| trits.get(*i) != BalancedTrit::Zero) {
+            return Err(Error::NonCanonicalTrits { offset: i });
+        }
+        while trits.len() > len {
+            trits.pop();
+        }
+
+        Ok(trits)
+    }
+}
+
+fn validate(bytes: &[u8]) -> Result<(), Error> {
+    match bytes.iter().position(|b| *b > 242) {
+        Some(offset) => Err(Error::InvalidByte {
+            offset,
+            value: bytes[offset],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// The trit at `index`, or `None` if its byte is above 242.
+fn checked_trit(bytes: &[u8], index: usize) -> Option<BalancedTrit> {
+    match bytes[index / 5] {
+        byte if byte > 242 => None,
+        byte => Some(decode_t5b1(byte)[index % 5]),
+    }
+}
+
+/// Packs 5 trits per byte; `bytes` must hold exactly a fifth of the trits.
+fn encode(trits: &[BalancedTrit], bytes: &mut [u8]) {
+    let done = simd::t1b1_to_t5b1(trits, bytes);
+
+    for (byte, group) in bytes.iter_mut().zip(trits.chunks(5)).skip(done) {
+        *byte = encode_group(group);
+    }
+}
+
+fn encode_group(group: &[BalancedTrit]) -> u8 {
+    let a = (group[0] as i8
+        + group[1] as i8 * 3
+        + group[2] as i8 * 9
+        + group[3] as i8 * 27
+        + group[4] as i8 * 81) as i16;
+
+    let a = if a < 0 { a + 243 } else { a };
+
+    a as u8
+}
+
+/// Ranks a byte by its trits, least significant first, so that comparing ranks compares the
+/// trits lexicographically. Invalid bytes rank after all valid ones.
+fn trit_rank(byte: u8) -> u16 {
+    if byte > 242 {
+        return byte as u16;
+    }
+
+    let mut value = (byte as u16 + 121) % 243;
+    let mut rank = 0;
+
+    for _ in 0..5 {
+        rank = rank * 3 + value % 3;
+        value /= 3;
+    }
+
+    rank
+}
+
+/// Compares lexicographically by trits like `T1B1`, and not by byte values.
+impl Ord for T5B1 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let rank = |b: &u8| trit_rank(*b);
+        self.0.iter().map(rank).cmp(other.0.iter().map(rank))
+    }
+}
+
+impl PartialOrd for T5B1 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<T1B1> for T5B1 {
+    fn eq(&self, other: &T1B1) -> bool {
+        self.0.len() * 5 == other.len()
+            && self.is_canonical()
+            && self
+                .0
+                .iter()
+                .zip(other.as_slice().chunks(5))
+                .all(|(byte, trits)| decode_t5b1(*byte) == trits)
+    }
+}
+
+impl PartialEq<T5B1> for T1B1 {
+    fn eq(&self, other: &T5B1) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<T3B1> for T5B1 {
+    fn eq(&self, other: &T3B1) -> bool {
+        self.0.len() * 5 == other.len() * 3
+            && self.is_canonical()
+            && self
+                .0
+                .iter()
+                .flat_map(|byte| decode_t5b1(*byte))
+                .eq(other.as_slice().iter().flat_map(|tryte| tryte.to_trits()))
+    }
+}
+
+impl PartialEq<T5B1> for T3B1 {
+    fn eq(&self, other: &T5B1) -> bool {
+        other == self
+    }
 }
 
 impl Encoding for T5B1 {
@@ -35,7 +278,85 @@ impl Encoding for T5B1 {
     }
 }
 
+impl TritAccess for T5B1Ref<'_> {
+    fn trit_len(&self) -> usize {
+        self.0.len() * 5
+    }
+
+    fn trit(&self, index: usize) -> BalancedTrit {
+        decode_t5b1(self.0[index / 5])[index % 5]
+    }
+
+    fn checked_trit(&self, index: usize) -> Option<BalancedTrit> {
+        checked_trit(self.0, index)
+    }
+}
+
+impl TritAccess for T5B1 {
+    fn trit_len(&self) -> usize {
+        self.0.len() * 5
+    }
+
+    fn trit(&self, index: usize) -> BalancedTrit {
+        decode_t5b1(self.0[index / 5])[index % 5]
+    }
+
+    fn checked_trit(&self, index: usize) -> Option<BalancedTrit> {
+        checked_trit(&self.0, index)
+    }
+}
+
+/// Prints the bytes as hex, or the trits with `{:#}`. Bytes that aren't canonical have no
+/// trits, so `{:#}` prints them as hex as well.
+impl fmt::Display for T5B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() && self.is_canonical() {
+            T1B1::from(self.clone()).fmt(f)
+        } else {
+            text::write_hex(f, &self.0, false)
+        }
+    }
+}
+
+/// Shows the number of bytes and trits, the bytes as hex, and the trits if every byte is valid.
+impl fmt::Debug for T5B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = f.debug_struct("T5B1");
+        s.field("len", &self.0.len())
+            .field("trits", &(self.0.len() * 5))
+            .field("bytes", &self.to_hex());
+        if self.is_canonical() {
+            s.field("value", &format!("{:#}", self));
+        }
+        s.finish()
+    }
+}
+
+impl fmt::LowerHex for T5B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::write_hex(f, &self.0, false)
+    }
+}
+
+impl fmt::UpperHex for T5B1 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        text::write_hex(f, &self.0, true)
+    }
+}
+
+/// The packed bytes themselves, each in 0..=242.
+impl RawBytes for T5B1 {
+    fn raw_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
 impl From<T1B1> for T5B1 {
+    #[allow(clippy::manual_is_multiple_of)]
     fn from(input: T1B1) -> T5B1 {
         let n = input.len();
         if n % 5 != 0 {
@@ -43,27 +364,16 @@ impl From<T1B1> for T5B1 {
         }
 
         let mut bytes = vec![0u8; n / 5];
-        let mut j = 0;
 
-        (0..n).step_by(5).for_each(|i| {
-            let a = (input.get_as_i8(i + 0)
-                + input.get_as_i8(i + 1) * 3
-                + input.get_as_i8(i + 2) * 9
-                + input.get_as_i8(i + 3) * 27
-                + input.get_as_i8(i + 4) * 81) as i16;
-
-            let a = if a < 0 { a + 243 } else { a };
-
-            bytes[j] = a as u8;
-
-            j += 1;
-        });
+        encode(input.as_slice(), &mut bytes);
 
         Self(bytes)
     }
 }
 
 impl From<T3B1> for T5B1 {
+    // The `+ 0` offsets keep the five trytes and three bytes aligned.
+    #[allow(clippy::identity_op, clippy::manual_is_multiple_of)]
     fn from(input: T3B1) -> T5B1 {
         let n = input.len();
         if (n * 3) % 5 != 0 {
@@ -103,6 +413,30 @@ impl From<T3B1> for T5B1 {
     }
 }
 
+/// Pads the last byte with zero trits, if the number of trits is not a multiple of 5.
+impl FromIterator<BalancedTrit> for T5B1 {
+    fn from_iter<I: IntoIterator<Item = BalancedTrit>>(iter: I) -> Self {
+        let mut bytes = Vec::new();
+        let mut group = [BalancedTrit::Zero; 5];
+        let mut n = 0;
+
+        for trit in iter {
+            group[n] = trit;
+            n += 1;
+            if n == 5 {
+                bytes.push(encode_group(&group));
+                n = 0;
+            }
+        }
+        if n > 0 {
+            group[n..].fill(BalancedTrit::Zero);
+            bytes.push(encode_group(&group));
+        }
+
+        Self(bytes)
+    }
+}
+
 impl IntoIterator for T5B1 {
     type Item = u8;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -111,3 +445,149 @@ impl IntoIterator for T5B1 {
         self.0.into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tritvec::TritVec;
+
+    #[test]
+    fn validated_construction() {
+        assert_eq!(Ok(vec![0, 242]), T5B1::from_bytes(&[0, 242]).map(|b| b.0));
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 2,
+                value: 255
+            }),
+            T5B1::from_vec(vec![0, 1, 255]).map(|b| b.0)
+        );
+
+        let bytes = vec![1, 2, 3];
+        let ptr = bytes.as_ptr();
+        let t5b1 = T5B1::from_vec(bytes).unwrap();
+        assert_eq!(ptr, t5b1.as_bytes().as_ptr());
+        let bytes = t5b1.into_bytes();
+        assert_eq!(ptr, bytes.as_ptr());
+    }
+
+    #[test]
+    fn borrowed_bytes() {
+        let buffer = [1u8, 122, 121];
+        let view = T5B1Ref::new(&buffer).unwrap();
+
+        assert_eq!(3, view.len());
+        assert_eq!(122, view.get(1));
+        assert_eq!("10000-----11111", T1B1::from(view).to_string());
+        assert_eq!(&buffer[..], view.to_t5b1().as_bytes());
+        assert!(T5B1Ref::new(&[243]).is_err());
+
+        let invalid = T5B1::from_u8(&[250]);
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 0,
+                value: 250
+            }),
+            invalid.borrowed().decode_strict(5)
+        );
+    }
+
+    #[test]
+    fn canonical_bytes() {
+        assert!(T5B1::from_u8(&[0, 121, 122, 242]).is_canonical());
+        assert!(!T5B1::from_u8(&[0, 243]).is_canonical());
+        assert!(!T5B1::from_u8(&[255]).is_canonical());
+    }
+
+    #[test]
+    fn strict_decoding() {
+        // "1-01" padded with a single zero trit.
+        let bytes: T5B1 = T1B1::from("1-010").into();
+
+        assert_eq!(
+            Ok("1-01".to_string()),
+            bytes.decode_strict(4).map(|t| t.to_string())
+        );
+        assert_eq!(
+            Ok("1-010".to_string()),
+            bytes.decode_strict(5).map(|t| t.to_string())
+        );
+        assert_eq!(
+            Err(Error::NonCanonicalTrits { offset: 3 }),
+            bytes.decode_strict(3).map(|t| t.to_string())
+        );
+        assert_eq!(
+            Err(Error::InvalidByteLength {
+                len: 1,
+                expected: 2
+            }),
+            bytes.decode_strict(6).map(|t| t.to_string())
+        );
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 1,
+                value: 250
+            }),
+            T5B1::from_u8(&[0, 250])
+                .decode_strict(10)
+                .map(|t| t.to_string())
+        );
+        assert_eq!(Ok(0), T5B1::new().decode_strict(0).map(|t| t.len()));
+    }
+
+    #[test]
+    fn encode_into_buffer() {
+        let trits: T1B1 = "10000-----11111".into();
+        let mut buffer = [0xaau8; 4];
+
+        assert_eq!(Ok(3), T5B1::encode_into(&trits, &mut buffer));
+        assert_eq!([1, 122, 121, 0xaa], buffer);
+        assert_eq!(
+            Err(Error::BufferTooSmall {
+                len: 2,
+                required: 3
+            }),
+            T5B1::encode_into(&trits, &mut buffer[..2])
+        );
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        let bytes: T5B1 = T1B1::from("10000-----11111").into();
+
+        assert_eq!("017a79", bytes.to_hex());
+        assert_eq!(Ok(vec![1, 122, 121]), T5B1::from_hex("017A79").map(|b| b.0));
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 1,
+                value: 243
+            }),
+            T5B1::from_hex("01f3").map(|b| b.0)
+        );
+    }
+
+    #[test]
+    fn base64_round_trip() {
+        let bytes = T5B1::from_u8(&[0, 1, 121, 122, 242]);
+
+        assert_eq!("AAF5evI=", bytes.to_base64());
+        assert_eq!(Ok(bytes.0), T5B1::from_base64("AAF5evI=").map(|b| b.0));
+    }
+
+    #[test]
+    fn formatting_modes() {
+        let bytes: T5B1 = T1B1::from("10000-----").into();
+
+        assert_eq!("017a", format!("{}", bytes));
+        assert_eq!("017a", format!("{:x}", bytes));
+        assert_eq!("017A", format!("{:X}", bytes));
+        assert_eq!("10000-----", format!("{:#}", bytes));
+        assert_eq!("00f3", format!("{:#}", T5B1::from_u8(&[0, 243])));
+
+        let mut vec: TritVec<T5B1> = TritVec::new();
+        vec.push("10000-----");
+
+        assert_eq!("017a", format!("{:x}", vec));
+        assert_eq!("10000-----", format!("{:#}", vec));
+        assert_eq!("017a", vec.to_hex());
+    }
+}
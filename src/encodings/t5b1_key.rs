@@ -0,0 +1,339 @@
+//! Encoding, where 5 trits are stored in a single byte, so that the bytes sort like the numbers
+//! the sequences represent, e.g. as keys of an ordered key-value store.
+//!
+//! The most significant group comes first, and every byte is the value of its 5 trits offset by
+//! 121, i.e. `-----` is 0 and `11111` is 242. Sequences whose length is not a multiple of 5 are
+//! padded with zero trits at the most significant end, which doesn't change their value.
+//!
+//! Advantages:
+//!     * Comparing bytes compares values, for keys of equal length.
+//!     * Same density as T5B1.
+//! Disadvantages:
+//!     * Keys of different lengths don't sort by value as bytes, compare them with `cmp_keys`.
+
+use super::iter::TritAccess;
+use super::t1b1::{decode_t5b1, T1B1};
+use super::t5b1::T5B1;
+use super::{Encoding, RawBytes};
+use crate::error::Error;
+use crate::text;
+use crate::trit::BalancedTrit;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::FromIterator;
+
+/// The byte of five zero trits.
+const ZERO_GROUP: u8 = 121;
+
+/// Unlike the other encodings, keys are ordered bytewise and not lexicographically by trits,
+/// which equals value order only for keys of equal length; use `cmp_keys` otherwise.
+///
+/// Every constructor checks the bytes, so they are always in 0..=242.
+#[derive(Clone, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct T5B1Key(Vec<u8>);
+
+impl T5B1Key {
+    /// Copies the bytes, after checking that each of them is in 0..=242.
+    pub fn from_bytes(input: &[u8]) -> Result<Self, Error> {
+        validate(input)?;
+        Ok(Self(Vec::from(input)))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// The next larger key of the same length, or `None` if all trits are one. Useful as the
+    /// exclusive end of a range scan.
+    pub fn successor(&self) -> Option<Self> {
+        let mut bytes = self.0.clone();
+
+        for byte in bytes.iter_mut().rev() {
+            if *byte < 242 {
+                *byte += 1;
+                return Some(Self(bytes));
+            }
+            *byte = 0;
+        }
+
+        None
+    }
+
+    /// Converts bytes that are known to be canonical, e.g. because `T5B1` encoded them itself.
+    fn from_canonical(input: &T5B1) -> Self {
+        Self(
+            input
+                .as_bytes()
+                .iter()
+                .rev()
+                .map(|b| from_t5b1(*b))
+                .collect(),
+        )
+    }
+
+    /// Decodes exactly `len` trits, like `T5B1::decode_strict`.
+    pub fn decode_strict(&self, len: usize) -> Result<T1B1, Error> {
+        T5B1::from(self.clone()).decode_strict(len)
+    }
+}
+
+/// Compares two keys by value without decoding them, even if their lengths differ: the shorter
+/// key is treated as if padded with zero groups (byte 121) at its most significant end.
+pub fn cmp_keys(a: &[u8], b: &[u8]) -> Ordering {
+    let len = a.len().max(b.len());
+    let byte = |key: &[u8], i: usize| match (i + key.len()).checked_sub(len) {
+        Some(i) => key[i],
+        None => ZERO_GROUP,
+    };
+
+    (0..len)
+        .map(|i| byte(a, i).cmp(&byte(b, i)))
+        .find(|ordering| *ordering != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+fn validate(bytes: &[u8]) -> Result<(), Error> {
+    match bytes.iter().position(|b| *b > 242) {
+        Some(offset) => Err(Error::InvalidByte {
+            offset,
+            value: bytes[offset],
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Maps a key byte to the `T5B1` byte of the same trits.
+#[inline]
+pub(crate) fn to_t5b1(byte: u8) -> u8 {
+    if byte > 242 {
+        panic!("Invalid T5B1Key byte");
+    }
+    ((byte as u16 + 122) % 243) as u8
+}
+
+#[inline]
+fn from_t5b1(byte: u8) -> u8 {
+    if byte > 242 {
+        panic!("Invalid T5B1 byte");
+    }
+    ((byte as u16 + 121) % 243) as u8
+}
+
+impl Encoding for T5B1Key {
+    fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Adds the trits as the new most significant end.
+    fn add(&mut self, trits: T1B1) {
+        let mut key: Self = trits.into();
+        key.0.extend_from_slice(&self.0);
+        self.0 = key.0;
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Yields the least significant trit first like every other encoding, i.e. the last byte
+/// stored comes first.
+impl TritAccess for T5B1Key {
+    fn trit_len(&self) -> usize {
+        self.0.len() * 5
+    }
+
+    fn trit(&self, index: usize) -> BalancedTrit {
+        let byte = self.0[self.0.len() - 1 - index / 5];
+        decode_t5b1(to_t5b1(byte))[index % 5]
+    }
+}
+
+/// Hex of the bytes like `T5B1`; `{:#}` shows the trits instead.
+impl fmt::Display for T5B1Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            for trit in self.trits() {
+                trit.fmt(f)?;
+            }
+            Ok(())
+        } else {
+            text::write_hex(f, &self.0, false)
+        }
+    }
+}
+
+impl fmt::Debug for T5B1Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("T5B1Key")
+            .field("len", &self.0.len())
+            .field("trits", &(self.0.len() * 5))
+            .field("bytes", &self.to_hex())
+            .field("value", &format!("{:#}", self))
+            .finish()
+    }
+}
+
+impl RawBytes for T5B1Key {
+    fn raw_bytes(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+/// Fails for bytes above 242, see `T5B1::is_canonical`.
+impl TryFrom<T5B1> for T5B1Key {
+    type Error = Error;
+
+    fn try_from(input: T5B1) -> Result<Self, Error> {
+        validate(input.as_bytes())?;
+        Ok(Self::from_canonical(&input))
+    }
+}
+
+impl From<T5B1Key> for T5B1 {
+    fn from(input: T5B1Key) -> T5B1 {
+        T5B1::from_u8(
+            &input
+                .0
+                .iter()
+                .rev()
+                .map(|b| to_t5b1(*b))
+                .collect::<Vec<u8>>(),
+        )
+    }
+}
+
+/// Pads the trits with zeros at the most significant end, see `decode_strict`.
+impl From<T1B1> for T5B1Key {
+    fn from(input: T1B1) -> Self {
+        input.trits().collect()
+    }
+}
+
+impl From<T5B1Key> for T1B1 {
+    fn from(input: T5B1Key) -> T1B1 {
+        input.trits().collect()
+    }
+}
+
+/// Expects the least significant trit first and pads the most significant group with zeros.
+impl FromIterator<BalancedTrit> for T5B1Key {
+    fn from_iter<I: IntoIterator<Item = BalancedTrit>>(iter: I) -> Self {
+        Self::from_canonical(&T5B1::from_iter(iter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::numeric::Numeric;
+    use crate::random::TritRng;
+
+    #[test]
+    fn byte_order_is_numeric_order() {
+        let mut rng = TritRng::seeded(0x6b65_7973);
+        // 243 trits, like a hash, so the most significant byte is padded.
+        let mut samples = (0..256).map(|_| rng.t1b1(243)).collect::<Vec<T1B1>>();
+        samples.sort_by_key(|trits| Numeric(trits.clone()));
+
+        let keys = samples
+            .iter()
+            .cloned()
+            .map(T5B1Key::from)
+            .collect::<Vec<_>>();
+
+        for (pair, keys) in samples.windows(2).zip(keys.windows(2)) {
+            assert_eq!(
+                Numeric(pair[0].clone()).cmp(&Numeric(pair[1].clone())),
+                cmp_keys(keys[0].as_bytes(), keys[1].as_bytes())
+            );
+            assert!(keys[0] <= keys[1]);
+        }
+    }
+
+    #[test]
+    fn compare_keys_of_different_lengths() {
+        let mut rng = TritRng::seeded(0x6c65_6e73);
+
+        for i in 0..169 {
+            let a = rng.t1b1(i % 13 * 5);
+            let b = rng.t1b1(i / 13 * 5);
+            let (ka, kb) = (T5B1Key::from(a.clone()), T5B1Key::from(b.clone()));
+
+            assert_eq!(
+                Numeric(a).cmp(&Numeric(b)),
+                cmp_keys(ka.as_bytes(), kb.as_bytes())
+            );
+        }
+
+        // 1 is larger than -1 + 3 = 2 as bytes, but not as values.
+        let one: T5B1Key = T1B1::from("1").into();
+        let two: T5B1Key = T1B1::from("-1000000").into();
+        assert!(one > two);
+        assert_eq!(Ordering::Less, cmp_keys(one.as_bytes(), two.as_bytes()));
+        assert_eq!(Ordering::Equal, cmp_keys(&[121, 121, 122], &[122]));
+    }
+
+    #[test]
+    fn most_significant_byte_first() {
+        // -1 + 3 = 2 in the lower group, 1 in the upper one.
+        let key: T5B1Key = T1B1::from("-1000100").into();
+
+        assert_eq!(vec![122, 123], key.as_bytes());
+        assert_eq!("7a7b", key.to_hex());
+        assert_eq!("-100010000", format!("{:#}", key));
+        assert_eq!(Ok(T1B1::from("-1000100")), key.decode_strict(8));
+        assert_eq!(
+            Err(Error::InvalidByteLength {
+                len: 2,
+                expected: 1
+            }),
+            key.decode_strict(5)
+        );
+    }
+
+    #[test]
+    fn t5b1_round_trip() {
+        let input: T5B1 = TritRng::seeded(0x7435_6231).t1b1(50).into();
+        let key = T5B1Key::try_from(input.clone()).unwrap();
+
+        assert_eq!(input, T5B1::from(key.clone()));
+        assert_eq!(T1B1::from(input), T1B1::from(key));
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 1,
+                value: 250
+            }),
+            T5B1Key::try_from(T5B1::from_u8(&[0, 250]))
+        );
+    }
+
+    #[test]
+    fn successor_carries() {
+        let key = T5B1Key::from_bytes(&[3, 242]).unwrap();
+
+        assert_eq!(vec![4, 0], key.successor().unwrap().into_bytes());
+        assert_eq!(None, T5B1Key::from_bytes(&[242, 242]).unwrap().successor());
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 0,
+                value: 243
+            }),
+            T5B1Key::from_bytes(&[243])
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! Encoding, where 5 trits are stored in a single byte, as a balanced `i8` in -121..=121.
+//!
+//! This is the byte convention of other IOTA stacks; `T5B1` stores the same value modulo 243
+//! instead. Converting between the two only changes the representation of negative values.
+//!
+//! Advantages:
+//!     * Reads and writes buffers of stacks that use signed bytes.
+//!     * The byte is the value of its trits.
+//! Disadvantages:
+//!     * Same as T5B1.
+
+use super::iter::TritAccess;
+use super::t1b1::{decode_t5b1, T1B1};
+use super::t5b1::T5B1;
+use super::{Encoding, RawBytes};
+use crate::error::Error;
+use crate::text;
+use crate::trit::BalancedTrit;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::iter::FromIterator;
+
+#[derive(Clone, Default, Eq, Hash, PartialEq)]
+pub struct T5B1Signed(Vec<i8>);
+
+impl T5B1Signed {
+    /// Copies the values, after checking that each of them is in -121..=121. An invalid value
+    /// is reported as its two's complement byte, like `raw_bytes` stores it, e.g. -122 as 134.
+    pub fn from_i8(input: &[i8]) -> Result<Self, Error> {
+        match input.iter().position(|v| !(-121..=121).contains(v)) {
+            Some(offset) => Err(Error::InvalidByte {
+                offset,
+                value: input[offset] as u8,
+            }),
+            None => Ok(Self(Vec::from(input))),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> i8 {
+        self.0[index]
+    }
+
+    pub fn as_values(&self) -> &[i8] {
+        &self.0
+    }
+
+    pub fn into_values(self) -> Vec<i8> {
+        self.0
+    }
+
+    /// Converts bytes that are known to be canonical, e.g. because `T5B1` encoded them itself.
+    fn from_canonical(input: &T5B1) -> Self {
+        Self(input.as_bytes().iter().map(|b| to_signed(*b)).collect())
+    }
+}
+
+/// Maps a value to the byte `T5B1` stores for it.
+#[inline]
+pub(crate) fn to_unsigned(value: i8) -> u8 {
+    (value as i16).rem_euclid(243) as u8
+}
+
+/// Maps a valid `T5B1` byte to its value.
+#[inline]
+fn to_signed(byte: u8) -> i8 {
+    if byte > 242 {
+        panic!("Invalid T5B1 byte");
+    }
+    if byte > 121 {
+        (byte as i16 - 243) as i8
+    } else {
+        byte as i8
+    }
+}
+
+impl Encoding for T5B1Signed {
+    fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    fn add(&mut self, trits: T1B1) {
+        let values: Self = trits.into();
+        self.0.extend(values.0);
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl TritAccess for T5B1Signed {
+    fn trit_len(&self) -> usize {
+        self.0.len() * 5
+    }
+
+    fn trit(&self, index: usize) -> BalancedTrit {
+        decode_t5b1(to_unsigned(self.0[index / 5]))[index % 5]
+    }
+}
+
+/// Compares lexicographically by trits like `T5B1`, and not by values.
+impl Ord for T5B1Signed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.trits().cmp(other.trits())
+    }
+}
+
+impl PartialOrd for T5B1Signed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<T5B1> for T5B1Signed {
+    fn eq(&self, other: &T5B1) -> bool {
+        self.0.len() == other.len()
+            && self
+                .0
+                .iter()
+                .zip(other.as_bytes())
+                .all(|(value, byte)| to_unsigned(*value) == *byte)
+    }
+}
+
+impl PartialEq<T5B1Signed> for T5B1 {
+    fn eq(&self, other: &T5B1Signed) -> bool {
+        other == self
+    }
+}
+
+/// Hex of the raw bytes like `T5B1`; `{:#}` shows the trits instead.
+impl fmt::Display for T5B1Signed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            for trit in self.trits() {
+                trit.fmt(f)?;
+            }
+            Ok(())
+        } else {
+            text::write_hex(f, &self.raw_bytes(), false)
+        }
+    }
+}
+
+impl fmt::Debug for T5B1Signed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("T5B1Signed")
+            .field("len", &self.0.len())
+            .field("trits", &(self.0.len() * 5))
+            .field("bytes", &self.to_hex())
+            .field("value", &format!("{:#}", self))
+            .finish()
+    }
+}
+
+/// The bytes are the two's complement of the values.
+impl RawBytes for T5B1Signed {
+    fn raw_bytes(&self) -> Vec<u8> {
+        self.0.iter().map(|v| *v as u8).collect()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_i8(&bytes.iter().map(|b| *b as i8).collect::<Vec<i8>>())
+    }
+}
+
+/// Fails for bytes above 242, see `T5B1::is_canonical`.
+impl TryFrom<T5B1> for T5B1Signed {
+    type Error = Error;
+
+    fn try_from(input: T5B1) -> Result<Self, Error> {
+        let bytes = input.as_bytes();
+
+        match bytes.iter().position(|b| *b > 242) {
+            Some(offset) => Err(Error::InvalidByte {
+                offset,
+                value: bytes[offset],
+            }),
+            None => Ok(Self::from_canonical(&input)),
+        }
+    }
+}
+
+impl From<T5B1Signed> for T5B1 {
+    fn from(input: T5B1Signed) -> T5B1 {
+        T5B1::from_u8(&input.0.iter().map(|v| to_unsigned(*v)).collect::<Vec<u8>>())
+    }
+}
+
+/// Pads the last byte with zero trits, if the number of trits is not a multiple of 5.
+impl From<T1B1> for T5B1Signed {
+    fn from(input: T1B1) -> Self {
+        input.trits().collect()
+    }
+}
+
+impl From<T5B1Signed> for T1B1 {
+    fn from(input: T5B1Signed) -> T1B1 {
+        let mut trits = T1B1::with_capacity(input.0.len() * 5);
+
+        for value in input.0 {
+            for trit in decode_t5b1(to_unsigned(value)).iter() {
+                trits.push_internal(*trit);
+            }
+        }
+
+        trits
+    }
+}
+
+/// Pads the last byte with zero trits, if the number of trits is not a multiple of 5.
+impl FromIterator<BalancedTrit> for T5B1Signed {
+    fn from_iter<I: IntoIterator<Item = BalancedTrit>>(iter: I) -> Self {
+        Self::from_canonical(&T5B1::from_iter(iter))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tritvec::TritVec;
+
+    #[test]
+    fn convert_byte_conventions() {
+        let unsigned = T5B1::from_u8(&[0, 1, 121, 122, 242]);
+        let signed = T5B1Signed::try_from(unsigned.clone()).unwrap();
+
+        assert_eq!(&[0, 1, 121, -121, -1], signed.as_values());
+        assert!(signed == unsigned);
+        assert_eq!(unsigned, T5B1::from(signed.clone()));
+        assert_eq!(T1B1::from(unsigned), T1B1::from(signed));
+    }
+
+    #[test]
+    fn values_are_balanced() {
+        let trits: T1B1 = "10000-1000-----11111".into();
+        let signed: T5B1Signed = trits.clone().into();
+
+        assert_eq!(&[1, 2, -121, 121], signed.as_values());
+        assert_eq!(trits.to_string(), format!("{:#}", signed));
+        assert!(signed.trits().eq(trits.trits()));
+    }
+
+    #[test]
+    fn pad_partial_bytes() {
+        let signed: T5B1Signed = T1B1::from("1").into();
+        assert_eq!(&[1], signed.as_values());
+
+        let mut vec: TritVec<T5B1Signed> = TritVec::new();
+        vec.push("1");
+        vec.push("-1");
+        assert_eq!("10000-1000", format!("{:#}", vec));
+    }
+
+    #[test]
+    fn raw_bytes_round_trip() {
+        let signed = T5B1Signed::from_i8(&[-121, -1, 0, 121]).unwrap();
+
+        assert_eq!("87ff0079", signed.to_hex());
+        assert_eq!(Ok(signed.clone()), T5B1Signed::from_hex("87ff0079"));
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 1,
+                value: 0x86
+            }),
+            T5B1Signed::from_hex("0086")
+        );
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 0,
+                value: 122
+            }),
+            T5B1Signed::from_i8(&[122])
+        );
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 0,
+                value: 134
+            }),
+            T5B1Signed::from_i8(&[-122])
+        );
+    }
+
+    #[test]
+    fn fail_for_invalid_t5b1_byte() {
+        assert_eq!(
+            Err(Error::InvalidByte {
+                offset: 1,
+                value: 243
+            }),
+            T5B1Signed::try_from(T5B1::from_u8(&[0, 243]))
+        );
+    }
+}
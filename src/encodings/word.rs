@@ -0,0 +1,387 @@
+//! Shared implementation of the encodings that pack trits into machine words.
+//!
+//! A word holds its trits least significant first, offset by the value with all trits set to
+//! one. So every trit is stored as the unbalanced digit `trit + 1`, the smallest value is the
+//! word 0, and comparing or incrementing words is plain integer arithmetic.
+
+/// 3^0 ..= 3^40
+pub(crate) const POW3: [u64; 41] = {
+    let mut pow = [1u64; 41];
+    let mut i = 1;
+
+    while i < 41 {
+        pow[i] = pow[i - 1] * 3;
+        i += 1;
+    }
+
+    pow
+};
+
+/// The trit at `index` of a word.
+#[inline]
+pub(crate) fn word_trit(word: u64, index: usize) -> crate::trit::BalancedTrit {
+    match (word / POW3[index]) % 3 {
+        0 => crate::trit::BalancedTrit::MinusOne,
+        1 => crate::trit::BalancedTrit::Zero,
+        _ => crate::trit::BalancedTrit::PlusOne,
+    }
+}
+
+macro_rules! word_encoding {
+    ($name:ident, $word:ty, $trits:expr, $bytes:expr) => {
+        use crate::encodings::iter::TritAccess;
+        use crate::encodings::word::{word_trit, POW3};
+        use crate::encodings::{t1b1::T1B1, t5b1::T5B1, Encoding};
+        use crate::error::Error;
+        use crate::trit::BalancedTrit;
+
+        use std::cmp::Ordering;
+        use std::convert::TryFrom;
+        use std::fmt;
+        use std::iter::FromIterator;
+
+        /// The number of trits per word.
+        pub const TRITS: usize = $trits;
+        /// The largest valid word, i.e. all trits set to one.
+        pub const MAX_WORD: $word = (POW3[TRITS] - 1) as $word;
+
+        #[derive(Clone, Default, Eq, Hash, PartialEq)]
+        pub struct $name(Vec<$word>);
+
+        impl $name {
+            /// Takes the words after checking that each of them is at most `MAX_WORD`.
+            pub fn from_words(words: Vec<$word>) -> Result<Self, Error> {
+                match words.iter().position(|w| *w > MAX_WORD) {
+                    Some(offset) => Err(Error::InvalidWord {
+                        offset,
+                        value: words[offset] as u64,
+                    }),
+                    None => Ok(Self(words)),
+                }
+            }
+
+            pub fn get(&self, index: usize) -> $word {
+                self.0[index]
+            }
+
+            pub fn as_words(&self) -> &[$word] {
+                &self.0
+            }
+
+            pub fn into_words(self) -> Vec<$word> {
+                self.0
+            }
+
+            /// The balanced value of the word at `index`.
+            pub fn value(&self, index: usize) -> i64 {
+                // `MAX_WORD / 2` exceeds `i64::MAX` for 40 trits, the difference doesn't.
+                (self.0[index] as i128 - (MAX_WORD / 2) as i128) as i64
+            }
+
+            /// Adds one to the whole sequence, read as a number with the first word being the
+            /// least significant. Returns `false` if it wrapped around to the smallest value.
+            pub fn increment(&mut self) -> bool {
+                for word in self.0.iter_mut() {
+                    if *word < MAX_WORD {
+                        *word += 1;
+                        return true;
+                    }
+                    *word = 0;
+                }
+                false
+            }
+
+            /// Compares the numbers the sequences represent, like `Numeric`, but word by word.
+            pub fn numeric_cmp(&self, other: &Self) -> Ordering {
+                let zero = MAX_WORD / 2;
+                let len = self.0.len().max(other.0.len());
+
+                (0..len)
+                    .rev()
+                    .map(|i| {
+                        let a = self.0.get(i).unwrap_or(&zero);
+                        let b = other.0.get(i).unwrap_or(&zero);
+                        a.cmp(b)
+                    })
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            }
+        }
+
+        /// Packs up to `TRITS` trits, padding the word with zero trits.
+        fn pack(trits: &[BalancedTrit]) -> $word {
+            (0..TRITS).rev().fold(0, |word, i| {
+                let trit = trits.get(i).map_or(0, |trit| *trit as i8);
+                word * 3 + (trit + 1) as $word
+            })
+        }
+
+        impl Encoding for $name {
+            fn new() -> Self {
+                Self(Vec::new())
+            }
+
+            fn with_capacity(capacity: usize) -> Self {
+                Self(Vec::with_capacity(capacity))
+            }
+
+            fn add(&mut self, trits: T1B1) {
+                let words: $name = trits.into();
+                self.0.extend(words.0);
+            }
+
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        impl TritAccess for $name {
+            fn trit_len(&self) -> usize {
+                self.0.len() * TRITS
+            }
+
+            fn trit(&self, index: usize) -> BalancedTrit {
+                word_trit(self.0[index / TRITS] as u64, index % TRITS)
+            }
+        }
+
+        /// Compares lexicographically by trits like the other encodings; see `numeric_cmp`
+        /// for the fast numeric comparison.
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.trits().cmp(other.trits())
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                for trit in self.trits() {
+                    trit.fmt(f)?;
+                }
+                Ok(())
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("len", &self.0.len())
+                    .field("trits", &(self.0.len() * TRITS))
+                    .field("value", &self.to_string())
+                    .finish()
+            }
+        }
+
+        /// Pads the last word with zero trits, if the number of trits is not a multiple of
+        /// `TRITS`.
+        impl From<T1B1> for $name {
+            fn from(input: T1B1) -> Self {
+                Self(input.as_slice().chunks(TRITS).map(pack).collect())
+            }
+        }
+
+        impl From<$name> for T1B1 {
+            fn from(input: $name) -> T1B1 {
+                input.trits().collect()
+            }
+        }
+
+        /// Pads the last word with zero trits, if the number of bytes is not a multiple of the
+        /// bytes per word. Fails for bytes above 242, see `T5B1::is_canonical`.
+        impl TryFrom<T5B1> for $name {
+            type Error = Error;
+
+            fn try_from(input: T5B1) -> Result<Self, Error> {
+                if let Some(offset) = input.as_bytes().iter().position(|b| *b > 242) {
+                    return Err(Error::InvalidByte {
+                        offset,
+                        value: input.get(offset),
+                    });
+                }
+
+                // Every byte is a balanced base 243 digit; offsetting it by 121 turns it into
+                // the unbalanced digit of the word, and a missing byte into 121.
+                let words = input
+                    .as_bytes()
+                    .chunks($bytes)
+                    .map(|bytes| {
+                        (0..$bytes).rev().fold(0, |word, j| {
+                            let digit = bytes.get(j).map_or(121, |b| (*b as u16 + 121) % 243);
+                            word * 243 + digit as $word
+                        })
+                    })
+                    .collect();
+
+                Ok(Self(words))
+            }
+        }
+
+        impl From<$name> for T5B1 {
+            fn from(input: $name) -> T5B1 {
+                let mut bytes = Vec::with_capacity(input.0.len() * $bytes);
+
+                for word in input.0.iter() {
+                    let mut word = *word;
+                    for _ in 0..$bytes {
+                        bytes.push((((word % 243) as u16 + 122) % 243) as u8);
+                        word /= 243;
+                    }
+                }
+
+                T5B1::from_vec(bytes).expect("word digits are valid T5B1 bytes")
+            }
+        }
+
+        /// Pads the last word with zero trits, if the number of trits is not a multiple of the
+        /// word size.
+        impl FromIterator<BalancedTrit> for $name {
+            fn from_iter<I: IntoIterator<Item = BalancedTrit>>(iter: I) -> Self {
+                let mut words = Vec::new();
+                let mut group = [BalancedTrit::Zero; TRITS];
+                let mut n = 0;
+
+                for trit in iter {
+                    group[n] = trit;
+                    n += 1;
+                    if n == TRITS {
+                        words.push(pack(&group));
+                        n = 0;
+                    }
+                }
+                if n > 0 {
+                    words.push(pack(&group[..n]));
+                }
+
+                Self(words)
+            }
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use super::*;
+
+            fn trits(n: usize) -> T1B1 {
+                let trits = (0..n)
+                    .map(|i| ((i * 7 + i / 3) % 3) as i8 - 1)
+                    .collect::<Vec<i8>>();
+                T1B1::from_i8(&trits)
+            }
+
+            #[test]
+            fn t1b1_round_trip() {
+                let input = trits(TRITS * 7);
+                let words: $name = input.clone().into();
+
+                assert_eq!(7, words.len());
+                assert_eq!(input.to_string(), words.to_string());
+                assert_eq!(input, T1B1::from(words));
+            }
+
+            #[test]
+            fn t5b1_round_trip() {
+                let input = trits(TRITS * 5);
+                let bytes: T5B1 = input.clone().into();
+                let words = $name::try_from(bytes.clone()).unwrap();
+
+                assert_eq!(input.to_string(), words.to_string());
+                assert_eq!(bytes, T5B1::from(words));
+                assert_eq!(
+                    Err(Error::InvalidByte {
+                        offset: 0,
+                        value: 250
+                    }),
+                    $name::try_from(T5B1::from_u8(&[250, 0, 0, 0]))
+                );
+            }
+
+            #[test]
+            fn word_values() {
+                let zero: $name = T1B1::from_i8(&[0; TRITS]).into();
+                let one: $name = std::iter::once(BalancedTrit::PlusOne).collect();
+                let min: $name = T1B1::from_i8(&[-1; TRITS]).into();
+
+                assert_eq!(0, zero.value(0));
+                assert_eq!(MAX_WORD / 2, zero.get(0));
+                assert_eq!(1, one.value(0));
+                assert_eq!(0, min.get(0));
+                assert_eq!(-((MAX_WORD / 2) as i64), min.value(0));
+                assert_eq!(
+                    Err(Error::InvalidWord {
+                        offset: 1,
+                        value: MAX_WORD as u64 + 1
+                    }),
+                    $name::from_words(vec![0, MAX_WORD + 1]).map(|w| w.len())
+                );
+            }
+
+            #[test]
+            fn largest_word_values() {
+                let half = (MAX_WORD / 2) as i64;
+                let words = $name::from_words(vec![MAX_WORD, MAX_WORD / 2 + 1]).unwrap();
+
+                assert_eq!(half, words.value(0));
+                assert_eq!(1, words.value(1));
+
+                let max: $name = T1B1::from_i8(&[1; TRITS]).into();
+                assert_eq!(MAX_WORD, max.get(0));
+                assert_eq!(half, max.value(0));
+            }
+
+            #[test]
+            fn pad_partial_words() {
+                let input = trits(TRITS + 7);
+                let words: $name = input.clone().into();
+                let collected: $name = input.trits().collect();
+
+                assert_eq!(2, words.len());
+                assert_eq!(collected, words);
+                assert_eq!(
+                    format!("{}{}", input, "0".repeat(TRITS - 7)),
+                    words.to_string()
+                );
+
+                // 3 bytes, i.e. 15 trits, leave the rest of the first word zero.
+                let bytes: T5B1 = trits(15).into();
+                let words = $name::try_from(bytes).unwrap();
+                assert_eq!(1, words.len());
+                assert_eq!(
+                    format!("{}{}", trits(15), "0".repeat(TRITS - 15)),
+                    words.to_string()
+                );
+            }
+
+            #[test]
+            fn increment_with_carry() {
+                let mut words = $name::from_words(vec![MAX_WORD - 1, 5]).unwrap();
+
+                assert!(words.increment());
+                assert_eq!(vec![MAX_WORD, 5], words.as_words());
+                assert!(words.increment());
+                assert_eq!(vec![0, 6], words.as_words());
+
+                let mut max = $name::from_words(vec![MAX_WORD; 2]).unwrap();
+                assert!(!max.increment());
+                assert_eq!(vec![0, 0], max.as_words());
+            }
+
+            #[test]
+            fn numeric_comparison() {
+                let small = $name::from_words(vec![MAX_WORD, MAX_WORD / 2]).unwrap();
+                let large = $name::from_words(vec![0, MAX_WORD / 2 + 1]).unwrap();
+                let short = $name::from_words(vec![MAX_WORD]).unwrap();
+
+                assert_eq!(Ordering::Less, small.numeric_cmp(&large));
+                assert_eq!(Ordering::Equal, small.numeric_cmp(&short));
+                assert_eq!(Ordering::Greater, large.numeric_cmp(&short));
+            }
+        }
+    };
+}
+
+pub(crate) use word_encoding;
@@ -0,0 +1,105 @@
+use std::fmt;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A tryte string that encodes bytes has an unpaired tryte, which isn't `9` padding.
+    OddTryteLength { len: usize },
+    /// A pair of trytes at the given tryte offset encodes a value above 255.
+    InvalidBytePair { offset: usize, value: u16 },
+    /// The decoded bytes are not valid UTF-8, starting at the given byte offset.
+    InvalidUtf8 { offset: usize },
+    /// The number of trits is not a multiple of the group size of the encoding.
+    InvalidTritLength { len: usize, multiple: usize },
+    /// The trits starting at the given offset are not in canonical form.
+    NonCanonicalTrits { offset: usize },
+    /// The decoded value doesn't fit into the requested number of bytes.
+    ByteOverflow { len: usize },
+    /// The byte at the given offset is not a valid value of the encoding.
+    InvalidByte { offset: usize, value: u8 },
+    /// Invalid hex digit or length at the given character offset.
+    InvalidHex { offset: usize },
+    /// Invalid base64 character, padding or length at the given character offset.
+    InvalidBase64 { offset: usize },
+    /// The output buffer holds `len` bytes, but `required` are needed.
+    BufferTooSmall { len: usize, required: usize },
+    /// There are `len` bytes, but the number of trits requires `expected`.
+    InvalidByteLength { len: usize, expected: usize },
+    /// Invalid decimal digit, sign or empty number at the given character offset.
+    InvalidDecimal { offset: usize },
+    /// The character at the given byte offset is not in the alphabet.
+    InvalidChar { offset: usize, value: char },
+    /// There are `len` trits or trytes, but `expected` are required.
+    InvalidLength { len: usize, expected: usize },
+    /// Reading or writing `requested` trits at the given offset, with only `available` left.
+    UnexpectedEnd {
+        offset: usize,
+        requested: usize,
+        available: usize,
+    },
+    /// The integer doesn't fit into `width` trits.
+    IntOverflow { value: i64, width: usize },
+    /// The word at the given offset is not a valid value of the encoding.
+    InvalidWord { offset: usize, value: u64 },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::OddTryteLength { len } => {
+                write!(f, "odd number of trytes ({}) without padding", len)
+            }
+            Error::InvalidBytePair { offset, value } => write!(
+                f,
+                "trytes at offset {} encode {}, which is not a byte",
+                offset, value
+            ),
+            Error::InvalidUtf8 { offset } => write!(f, "invalid UTF-8 at byte offset {}", offset),
+            Error::InvalidTritLength { len, multiple } => {
+                write!(f, "{} trits are not a multiple of {}", len, multiple)
+            }
+            Error::NonCanonicalTrits { offset } => {
+                write!(f, "non-canonical trits at offset {}", offset)
+            }
+            Error::ByteOverflow { len } => write!(f, "value doesn't fit into {} bytes", len),
+            Error::InvalidByte { offset, value } => {
+                write!(f, "invalid byte {} at offset {}", value, offset)
+            }
+            Error::InvalidHex { offset } => write!(f, "invalid hex at offset {}", offset),
+            Error::InvalidBase64 { offset } => write!(f, "invalid base64 at offset {}", offset),
+            Error::BufferTooSmall { len, required } => write!(
+                f,
+                "buffer of {} bytes is too small, {} are required",
+                len, required
+            ),
+            Error::InvalidByteLength { len, expected } => {
+                write!(f, "{} bytes, but {} are expected", len, expected)
+            }
+            Error::InvalidDecimal { offset } => {
+                write!(f, "invalid decimal at offset {}", offset)
+            }
+            Error::InvalidChar { offset, value } => {
+                write!(f, "invalid character {:?} at offset {}", value, offset)
+            }
+            Error::InvalidLength { len, expected } => {
+                write!(f, "length {}, but {} is required", len, expected)
+            }
+            Error::UnexpectedEnd {
+                offset,
+                requested,
+                available,
+            } => write!(
+                f,
+                "{} trits requested at offset {}, but only {} are available",
+                requested, offset, available
+            ),
+            Error::IntOverflow { value, width } => {
+                write!(f, "{} doesn't fit into {} trits", value, width)
+            }
+            Error::InvalidWord { offset, value } => {
+                write!(f, "invalid word {} at offset {}", value, offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
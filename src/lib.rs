@@ -1,12 +1,46 @@
+mod alphabet;
+pub mod binary;
+mod bitsliced;
+pub mod curl;
+mod cursor;
+mod dump;
 mod encodings;
+mod error;
+mod numeric;
+mod parse;
+pub mod pow;
+pub mod random;
+mod simd;
+mod text;
 mod trit;
+mod tritint;
 mod tritvec;
 mod tryte;
 
+pub use crate::alphabet::{DisplayTrits, DisplayTrytes, TritAlphabet, TryteAlphabet};
+pub use crate::bitsliced::{BitSliced128, BitSliced64, Lane128, Lane64};
+pub use crate::cursor::{TritReader, TritWriter, MAX_INT_WIDTH};
+pub use crate::dump::TritDump;
+pub use crate::error::Error;
+pub use crate::numeric::Numeric;
+pub use crate::parse::{ParseError, ParseOptions};
 pub use crate::trit::BalancedTrit;
 pub use crate::tryte::BalancedTryte;
 
-pub use crate::encodings::{t1b1::T1B1, t3b1::T3B1, t5b1::T5B1, Encoding};
+pub use crate::encodings::{
+    iter::{TritAccess, Trits, Trytes},
+    packed::{Packed, T15B3, T4B1, T8B2},
+    t1b1::T1B1,
+    t1b2::T1B2,
+    t20b4::T20B4,
+    t3b1::T3B1,
+    t40b8::T40B8,
+    t5b1::{T5B1Ref, T5B1},
+    t5b1_key::{cmp_keys, T5B1Key},
+    t5b1_signed::T5B1Signed,
+    Encoding, RawBytes,
+};
 //pub use crate::encodings::t9b2::T9B2;
 
+pub use crate::tritint::TritInt;
 pub use crate::tritvec::TritVec;
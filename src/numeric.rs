@@ -0,0 +1,132 @@
+//! Ordering of trit sequences by the balanced ternary number they represent, least significant
+//! trit first, instead of lexicographically.
+
+use crate::encodings::iter::TritAccess;
+
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// Compares the wrapped sequence by its numeric value, so trailing zeros don't matter and
+/// `"1"`, `"10"` and `"100"` are equal. Works for every encoding, through `TritAccess`.
+///
+/// Trits stored in invalid bytes, see `T5B1::is_canonical`, rank above every valid trit, like
+/// the invalid bytes in the `Ord` of `T5B1`, and equal to each other.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Numeric<T>(pub T);
+
+impl<T> Numeric<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: TritAccess> Numeric<T> {
+    /// The trit at `index` as -1, 0 or 1, zero past the end, and 2 for an invalid trit.
+    fn rank(&self, index: usize) -> i8 {
+        if index >= self.0.trit_len() {
+            return 0;
+        }
+        self.0.checked_trit(index).map_or(2, |trit| trit as i8)
+    }
+
+    /// The number of trits up to the last one that isn't zero.
+    fn significant(&self) -> usize {
+        (0..self.0.trit_len())
+            .rev()
+            .find(|i| self.rank(*i) != 0)
+            .map_or(0, |i| i + 1)
+    }
+}
+
+impl<T: TritAccess> Ord for Numeric<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (0..self.0.trit_len().max(other.0.trit_len()))
+            .rev()
+            .map(|i| self.rank(i).cmp(&other.rank(i)))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T: TritAccess> PartialOrd for Numeric<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: TritAccess> PartialEq for Numeric<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T: TritAccess> Eq for Numeric<T> {}
+
+/// Skips trailing zeros, to agree with `Eq`.
+impl<T: TritAccess> Hash for Numeric<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let significant = self.significant();
+
+        significant.hash(state);
+        for i in 0..significant {
+            self.rank(i).hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::{t1b1::T1B1, t3b1::T3B1, t5b1::T5B1};
+
+    use std::collections::HashSet;
+
+    fn numeric(s: &str) -> Numeric<T1B1> {
+        Numeric(s.into())
+    }
+
+    #[test]
+    fn compare_by_value() {
+        // 1 < 2 < 3, although "-1" < "01" < "1" lexicographically.
+        assert!(numeric("1") < numeric("-1"));
+        assert!(numeric("-1") < numeric("01"));
+        assert!(numeric("-") < numeric(""));
+        assert!(numeric("11-") < numeric("--1"));
+        assert_eq!(numeric("1"), numeric("100"));
+    }
+
+    #[test]
+    fn sort_trytes_by_value() {
+        let mut trytes: Vec<Numeric<T3B1>> = ["A9", "9A", "N", "Z", "9"]
+            .iter()
+            .map(|s| Numeric(T3B1::from(*s)))
+            .collect();
+        trytes.sort();
+
+        let sorted = trytes
+            .iter()
+            .map(|t| t.0.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(vec!["N", "Z", "9", "A9", "9A"], sorted);
+    }
+
+    #[test]
+    fn hash_agrees_with_eq() {
+        let mut set = HashSet::new();
+        set.insert(numeric("1-"));
+        set.insert(numeric("1-00"));
+        set.insert(numeric("-1"));
+
+        assert_eq!(2, set.len());
+    }
+
+    #[test]
+    fn rank_invalid_bytes_last() {
+        let valid = Numeric(T5B1::from_u8(&[1, 121]));
+        let invalid = Numeric(T5B1::from_u8(&[1, 243]));
+
+        assert!(valid < invalid);
+        assert_eq!(invalid, Numeric(T5B1::from_u8(&[1, 243, 0])));
+        assert_eq!(invalid, Numeric(T5B1::from_u8(&[1, 250])));
+    }
+}
@@ -0,0 +1,311 @@
+//! Configurable parsing of trit and tryte strings, e.g. for hand-typed or grouped input.
+
+use crate::alphabet::{TritAlphabet, TryteAlphabet};
+use crate::encodings::{t1b1::T1B1, t3b1::T3B1};
+use crate::error::Error;
+
+use std::fmt;
+
+/// Builds how `parse_t1b1` and `parse_t3b1` read a string. `new()` is strict: exact
+/// characters of the default alphabets, any length, stop at the first error.
+#[derive(Clone, Debug, Default)]
+pub struct ParseOptions {
+    trit_alphabet: TritAlphabet,
+    tryte_alphabet: TryteAlphabet,
+    case_insensitive: bool,
+    ignore_whitespace: bool,
+    separators: Vec<char>,
+    length: Option<usize>,
+    checksum: usize,
+    collect_errors: bool,
+}
+
+impl ParseOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts lower case letters and any whitespace.
+    pub fn lenient() -> Self {
+        Self::new().case_insensitive(true).ignore_whitespace(true)
+    }
+
+    pub fn trit_alphabet(mut self, alphabet: TritAlphabet) -> Self {
+        self.trit_alphabet = alphabet;
+        self
+    }
+
+    pub fn tryte_alphabet(mut self, alphabet: TryteAlphabet) -> Self {
+        self.tryte_alphabet = alphabet;
+        self
+    }
+
+    pub fn case_insensitive(mut self, yes: bool) -> Self {
+        self.case_insensitive = yes;
+        self
+    }
+
+    pub fn ignore_whitespace(mut self, yes: bool) -> Self {
+        self.ignore_whitespace = yes;
+        self
+    }
+
+    /// Skips these characters, e.g. `'.'` or `':'` between groups.
+    pub fn separators(mut self, separators: &[char]) -> Self {
+        self.separators = separators.to_vec();
+        self
+    }
+
+    /// Requires exactly `len` trits or trytes.
+    pub fn length(mut self, len: usize) -> Self {
+        self.length = Some(len);
+        self
+    }
+
+    /// Additionally accepts `len` trailing trits or trytes of checksum. Only has an effect
+    /// together with `length`.
+    pub fn checksum(mut self, len: usize) -> Self {
+        self.checksum = len;
+        self
+    }
+
+    /// Reports every invalid character instead of only the first one.
+    pub fn collect_errors(mut self, yes: bool) -> Self {
+        self.collect_errors = yes;
+        self
+    }
+
+    /// Returns the trits without the checksum, if there is one.
+    pub fn parse_t1b1(&self, s: &str) -> Result<T1B1, ParseError> {
+        self.parse_t1b1_with_checksum(s).map(|(trits, _)| trits)
+    }
+
+    pub fn parse_t1b1_with_checksum(&self, s: &str) -> Result<(T1B1, Option<T1B1>), ParseError> {
+        let trits = self.parse(s, |c| self.trit_alphabet.from_char(c))?;
+        let (value, checksum) = self.split_checksum(trits);
+
+        Ok((
+            value.into_iter().collect(),
+            checksum.map(|c| c.into_iter().collect()),
+        ))
+    }
+
+    /// Returns the trytes without the checksum, if there is one.
+    pub fn parse_t3b1(&self, s: &str) -> Result<T3B1, ParseError> {
+        self.parse_t3b1_with_checksum(s).map(|(trytes, _)| trytes)
+    }
+
+    pub fn parse_t3b1_with_checksum(&self, s: &str) -> Result<(T3B1, Option<T3B1>), ParseError> {
+        let trytes = self.parse(s, |c| self.tryte_alphabet.from_char(c))?;
+        let (value, checksum) = self.split_checksum(trytes);
+
+        Ok((
+            value.into_iter().collect(),
+            checksum.map(|c| c.into_iter().collect()),
+        ))
+    }
+
+    fn parse<T>(
+        &self,
+        s: &str,
+        from_char: impl Fn(char) -> Option<T>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = Vec::with_capacity(s.len());
+        let mut errors = Vec::new();
+
+        for (offset, c) in s.char_indices() {
+            if (self.ignore_whitespace && c.is_whitespace()) || self.separators.contains(&c) {
+                continue;
+            }
+
+            let item = from_char(c).or_else(|| {
+                if self.case_insensitive {
+                    from_char(c.to_ascii_uppercase()).or_else(|| from_char(c.to_ascii_lowercase()))
+                } else {
+                    None
+                }
+            });
+
+            match item {
+                Some(item) => items.push(item),
+                None => {
+                    errors.push(Error::InvalidChar { offset, value: c });
+                    if !self.collect_errors {
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Some(expected) = self.length {
+            let with_checksum = self.checksum > 0 && items.len() == expected + self.checksum;
+            if errors.is_empty() && items.len() != expected && !with_checksum {
+                errors.push(Error::InvalidLength {
+                    len: items.len(),
+                    expected,
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(items)
+        } else {
+            Err(ParseError { errors })
+        }
+    }
+
+    fn split_checksum<T>(&self, mut items: Vec<T>) -> (Vec<T>, Option<Vec<T>>) {
+        match self.length {
+            Some(len) if items.len() > len => {
+                let checksum = items.split_off(len);
+                (items, Some(checksum))
+            }
+            _ => (items, None),
+        }
+    }
+}
+
+/// Everything that was wrong with the input; a single error unless errors are collected.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    errors: Vec<Error>,
+}
+
+impl ParseError {
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    pub fn first(&self) -> &Error {
+        &self.errors[0]
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        e.errors[0].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::Encoding;
+
+    #[test]
+    fn strict_by_default() {
+        let options = ParseOptions::new();
+
+        assert_eq!(Ok(3), options.parse_t3b1("ABC").map(|t| t.len()));
+        assert_eq!(
+            Err(Error::InvalidChar {
+                offset: 3,
+                value: ' '
+            }),
+            options.parse_t3b1("ABC DEF").map_err(|e| e.first().clone())
+        );
+        assert_eq!(
+            Err(Error::InvalidChar {
+                offset: 0,
+                value: 'a'
+            }),
+            options.parse_t3b1("abc").map_err(|e| e.first().clone())
+        );
+    }
+
+    #[test]
+    fn lenient_grouped_input() {
+        let trytes = ParseOptions::lenient()
+            .parse_t3b1("ABC def\n\tGHI ")
+            .unwrap();
+        assert_eq!("ABCDEFGHI", trytes.to_string());
+
+        let trits = ParseOptions::new()
+            .separators(&['.', '_'])
+            .parse_t1b1("10-.01_-")
+            .unwrap();
+        assert_eq!("10-01-", trits.to_string());
+
+        let trits = ParseOptions::lenient()
+            .trit_alphabet(TritAlphabet::N0P)
+            .parse_t1b1("p0n N")
+            .unwrap();
+        assert_eq!("10--", trits.to_string());
+    }
+
+    #[test]
+    fn required_length_and_checksum() {
+        let options = ParseOptions::lenient().length(6).checksum(3);
+
+        let (trytes, checksum) = options.parse_t3b1_with_checksum("MINI9T").unwrap();
+        assert_eq!(("MINI9T".to_string(), None), (trytes.to_string(), checksum));
+
+        let (trytes, checksum) = options.parse_t3b1_with_checksum("MINI9T RIX").unwrap();
+        assert_eq!("MINI9T", trytes.to_string());
+        assert_eq!(Some("RIX".to_string()), checksum.map(|c| c.to_string()));
+
+        assert_eq!(
+            Err(Error::InvalidLength {
+                len: 8,
+                expected: 6
+            }),
+            options
+                .parse_t3b1("MINI9TRI")
+                .map_err(|e| e.first().clone())
+        );
+        assert_eq!(
+            Err(Error::InvalidLength {
+                len: 9,
+                expected: 6
+            }),
+            ParseOptions::new()
+                .length(6)
+                .parse_t1b1("10-01-110")
+                .map_err(|e| e.first().clone())
+        );
+    }
+
+    #[test]
+    fn collect_all_errors() {
+        let input = "A?C!E";
+
+        let fail_fast = ParseOptions::new().parse_t3b1(input).unwrap_err();
+        assert_eq!(1, fail_fast.errors().len());
+
+        let all = ParseOptions::new()
+            .collect_errors(true)
+            .parse_t3b1(input)
+            .unwrap_err();
+        assert_eq!(
+            &[
+                Error::InvalidChar {
+                    offset: 1,
+                    value: '?'
+                },
+                Error::InvalidChar {
+                    offset: 3,
+                    value: '!'
+                },
+            ],
+            all.errors()
+        );
+        assert_eq!(
+            "invalid character '?' at offset 1, invalid character '!' at offset 3",
+            all.to_string()
+        );
+    }
+}
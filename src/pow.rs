@@ -0,0 +1,262 @@
+//! Proof of work: searches the nonce of a transaction, until its Curl-P-81 hash ends with at
+//! least `min_weight_magnitude` zero trits.
+//!
+//! Every thread hashes 64 nonces at once, one per lane of a `BitSliced64` state.
+
+use crate::bitsliced::BitSliced64;
+use crate::curl::{next_index, CurlP81, HASH_LENGTH, ROUNDS, STATE_LENGTH};
+use crate::encodings::{t1b1::T1B1, Encoding};
+use crate::trit::BalancedTrit;
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub const TRANSACTION_LENGTH: usize = 2673;
+pub const NONCE_LENGTH: usize = 81;
+
+const NONCE_OFFSET: usize = TRANSACTION_LENGTH - NONCE_LENGTH;
+const LANES: u64 = 64;
+
+/// Allows stopping a running search from another thread.
+#[derive(Clone, Debug, Default)]
+pub struct Cancel(Arc<AtomicBool>);
+
+impl Cancel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Searches a nonce for the transaction using `threads` worker threads, and returns the
+/// transaction with its nonce field filled in, or `None` if the search was cancelled.
+pub fn search<T>(
+    transaction: T,
+    min_weight_magnitude: usize,
+    threads: usize,
+    cancel: &Cancel,
+) -> Option<T>
+where
+    T: Into<T1B1> + From<T1B1>,
+{
+    let transaction: T1B1 = transaction.into();
+
+    if transaction.len() != TRANSACTION_LENGTH {
+        panic!("Invalid transaction length");
+    }
+    if min_weight_magnitude > HASH_LENGTH {
+        panic!("Invalid minimum weight magnitude");
+    }
+
+    // Everything but the last chunk is independent of the nonce.
+    let mut curl = CurlP81::new();
+    let (head, tail) = transaction
+        .as_slice()
+        .split_at(TRANSACTION_LENGTH - HASH_LENGTH);
+    curl.absorb_slice(head);
+
+    let mut mid = BitSliced64::new(STATE_LENGTH);
+    for (index, trit) in curl.state().iter().enumerate() {
+        mid.fill(index, (*trit).into());
+    }
+    for (index, trit) in tail.iter().enumerate() {
+        mid.fill(index, *trit);
+    }
+
+    let batch = AtomicU64::new(0);
+    let nonce = Mutex::new(None);
+    let found = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            scope.spawn(|| {
+                while !found.load(Ordering::Relaxed) && !cancel.is_cancelled() {
+                    let first = batch.fetch_add(1, Ordering::Relaxed) * LANES;
+
+                    if let Some(n) = search_batch(&mid, first, min_weight_magnitude) {
+                        if !found.swap(true, Ordering::Relaxed) {
+                            *nonce.lock().unwrap() = Some(n);
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    let nonce = nonce_trits(nonce.into_inner().unwrap()?);
+    let mut trits = T1B1::with_capacity(TRANSACTION_LENGTH);
+
+    for trit in &transaction.as_slice()[..NONCE_OFFSET] {
+        trits.push_internal(*trit);
+    }
+    for trit in nonce.iter() {
+        trits.push_internal(*trit);
+    }
+
+    Some(trits.into())
+}
+
+/// Hashes the nonces `first..first + 64` and returns the first one that satisfies the weight.
+fn search_batch(mid: &BitSliced64, first: u64, mwm: usize) -> Option<u64> {
+    let mut state = mid.clone();
+    let nonce_start = HASH_LENGTH - NONCE_LENGTH;
+
+    for lane in 0..LANES {
+        let nonce = nonce_trits(first + lane);
+        for (i, trit) in nonce.iter().enumerate() {
+            state.set(nonce_start + i, lane as usize, *trit);
+        }
+    }
+
+    transform(&mut state);
+
+    let zeros = state.zero_lanes(HASH_LENGTH - mwm..HASH_LENGTH);
+
+    if zeros == 0 {
+        None
+    } else {
+        Some(first + zeros.trailing_zeros() as u64)
+    }
+}
+
+/// The balanced ternary representation of `nonce`, least significant trit first.
+fn nonce_trits(nonce: u64) -> [BalancedTrit; NONCE_LENGTH] {
+    let mut trits = [BalancedTrit::Zero; NONCE_LENGTH];
+    let mut n = nonce;
+
+    for trit in trits.iter_mut() {
+        if n == 0 {
+            break;
+        }
+        *trit = match n % 3 {
+            0 => BalancedTrit::Zero,
+            1 => BalancedTrit::PlusOne,
+            _ => BalancedTrit::MinusOne,
+        };
+        n = n / 3 + if n % 3 == 2 { 1 } else { 0 };
+    }
+
+    trits
+}
+
+/// The Curl-P-81 permutation, with the truth table expressed on bit planes.
+fn transform(state: &mut BitSliced64) {
+    let mut scratch = state.clone();
+
+    for _ in 0..ROUNDS {
+        scratch.clone_from(state);
+
+        let mut index = 0;
+        for target in 0..STATE_LENGTH {
+            let (a_pos, a_neg) = scratch.planes(index);
+            index = next_index(index);
+            let (b_pos, b_neg) = scratch.planes(index);
+
+            let a_zero = !(a_pos | a_neg);
+            let b_zero = !(b_pos | b_neg);
+
+            state.set_planes(
+                target,
+                (a_neg & !b_pos) | (a_zero & b_pos),
+                (a_pos & b_neg) | (a_zero & b_zero) | (a_neg & b_pos),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::t3b1::T3B1;
+
+    fn transaction() -> T1B1 {
+        let trits = (0..TRANSACTION_LENGTH)
+            .map(|i| ((i * 7 + i / 5) % 3) as i8 - 1)
+            .collect::<Vec<i8>>();
+        T1B1::from_i8(&trits)
+    }
+
+    fn weight(transaction: &T1B1) -> usize {
+        let hash = CurlP81::hash(transaction);
+        (0..HASH_LENGTH)
+            .rev()
+            .take_while(|i| hash.get(*i) == BalancedTrit::Zero)
+            .count()
+    }
+
+    #[test]
+    fn bitsliced_transform_matches_curl() {
+        let chunk = T1B1::from_i8(&[1, 0, -1, -1, 1].repeat(HASH_LENGTH / 5 + 1)[..HASH_LENGTH]);
+
+        let mut state = BitSliced64::new(STATE_LENGTH);
+        for (index, trit) in chunk.as_slice().iter().enumerate() {
+            state.fill(index, *trit);
+        }
+        transform(&mut state);
+
+        let expected = CurlP81::hash(&chunk);
+        for lane in [0, 17, 63] {
+            assert_eq!(
+                expected.to_string(),
+                T1B1::from(state.lane(lane)).to_string()[..HASH_LENGTH]
+            );
+        }
+    }
+
+    #[test]
+    fn balanced_nonce_trits() {
+        let trits = |n| {
+            nonce_trits(n)[..5]
+                .iter()
+                .map(|t| t.to_string())
+                .collect::<String>()
+        };
+
+        assert_eq!("00000", trits(0));
+        assert_eq!("10000", trits(1));
+        assert_eq!("-1000", trits(2));
+        assert_eq!("11111", trits(121));
+    }
+
+    #[test]
+    fn search_t1b1() {
+        let input = transaction();
+        let output = search(input.clone(), 7, 2, &Cancel::new()).unwrap();
+
+        assert!(weight(&output) >= 7);
+        assert_eq!(
+            input.to_string()[..NONCE_OFFSET],
+            output.to_string()[..NONCE_OFFSET]
+        );
+    }
+
+    #[test]
+    fn search_t3b1() {
+        let input: T3B1 = transaction().into();
+        let output = search(input, 5, 1, &Cancel::new()).unwrap();
+
+        assert!(weight(&output.into()) >= 5);
+    }
+
+    #[test]
+    fn cancel_search() {
+        let cancel = Cancel::new();
+        cancel.cancel();
+
+        assert!(search(transaction(), HASH_LENGTH, 4, &cancel).is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_for_invalid_length() {
+        let _ = search(T1B1::from_i8(&[0; 243]), 1, 1, &Cancel::new());
+    }
+}
@@ -0,0 +1,217 @@
+//! Uniformly distributed trits, trytes and encodings, e.g. for seeds and test data.
+//!
+//! Randomness comes from any `FnMut() -> u64`, from the built-in `SplitMix64` for reproducible
+//! output, or, with the `rand` feature, from any `rand_core::RngCore`. Every accepted `u64` is
+//! below 3^40 and provides 40 independent trits, so no value is more likely than another.
+
+use crate::encodings::{t1b1::T1B1, t3b1::T3B1, t5b1::T5B1};
+use crate::trit::BalancedTrit;
+use crate::tryte::BalancedTryte;
+
+/// 3^40, the largest power of 3 that fits into a `u64`.
+const POW_3_40: u64 = 12_157_665_459_056_928_801;
+const TRITS_PER_DRAW: u32 = 40;
+
+/// A source of uniformly distributed `u64`s.
+pub trait Entropy {
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<F: FnMut() -> u64> Entropy for F {
+    fn next_u64(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// Small, fast and deterministic; not suitable for secret seeds.
+#[derive(Clone, Debug)]
+pub struct SplitMix64(u64);
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+}
+
+impl Entropy for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Uses a `rand` generator, e.g. `OsRng` for seeds.
+#[cfg(feature = "rand")]
+#[derive(Clone, Debug)]
+pub struct RandEntropy<R>(pub R);
+
+#[cfg(feature = "rand")]
+impl<R: rand_core::RngCore> Entropy for RandEntropy<R> {
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}
+
+/// Draws trits from an entropy source.
+#[derive(Clone, Debug)]
+pub struct TritRng<E> {
+    entropy: E,
+    buffer: u64,
+    left: u32,
+}
+
+impl TritRng<SplitMix64> {
+    /// Always produces the same trits for the same seed.
+    pub fn seeded(seed: u64) -> Self {
+        Self::new(SplitMix64::new(seed))
+    }
+}
+
+impl<E: Entropy> TritRng<E> {
+    pub fn new(entropy: E) -> Self {
+        Self {
+            entropy,
+            buffer: 0,
+            left: 0,
+        }
+    }
+
+    pub fn trit(&mut self) -> BalancedTrit {
+        if self.left == 0 {
+            self.refill();
+        }
+
+        let digit = self.buffer % 3;
+        self.buffer /= 3;
+        self.left -= 1;
+
+        (digit as i8 - 1).into()
+    }
+
+    pub fn tryte(&mut self) -> BalancedTryte {
+        BalancedTryte::from_trits([self.trit(), self.trit(), self.trit()])
+    }
+
+    /// Returns `len` trits.
+    pub fn t1b1(&mut self, len: usize) -> T1B1 {
+        (0..len).map(|_| self.trit()).collect()
+    }
+
+    /// Returns `len` trytes.
+    pub fn t3b1(&mut self, len: usize) -> T3B1 {
+        (0..len).map(|_| self.tryte()).collect()
+    }
+
+    /// Returns `len` bytes, i.e. `len * 5` trits.
+    pub fn t5b1(&mut self, len: usize) -> T5B1 {
+        (0..len * 5).map(|_| self.trit()).collect()
+    }
+
+    /// Rejects values of 3^40 and above, since they would favour the lower digits.
+    fn refill(&mut self) {
+        self.buffer = loop {
+            let value = self.entropy.next_u64();
+            if value < POW_3_40 {
+                break value;
+            }
+        };
+        self.left = TRITS_PER_DRAW;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encodings::Encoding;
+
+    #[test]
+    fn seeded_is_reproducible() {
+        let a = TritRng::seeded(42).t1b1(100);
+        let b = TritRng::seeded(42).t1b1(100);
+        let c = TritRng::seeded(43).t1b1(100);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn lengths_of_encodings() {
+        let mut rng = TritRng::seeded(0);
+
+        assert_eq!(10, rng.t1b1(10).len());
+        assert_eq!(10, rng.t3b1(10).len());
+        assert_eq!(10, rng.t5b1(10).len());
+        assert!(rng.t5b1(100).is_canonical());
+    }
+
+    #[test]
+    fn rejects_values_above_range() {
+        // The first value is rejected, 0 is all minus ones, and 1 starts with a zero trit.
+        let mut values = vec![1, 0, u64::MAX];
+        let mut rng = TritRng::new(move || values.pop().unwrap());
+
+        let trits = rng.t1b1(41);
+        assert_eq!(
+            "-".repeat(40) + "0",
+            trits.trits().map(|t| t.to_string()).collect::<String>()
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn rand_entropy() {
+        /// Yields 0, 1, 2, ... like the closure below.
+        struct Counter(u64);
+
+        impl rand_core::RngCore for Counter {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                self.0 += 1;
+                self.0 - 1
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        let mut n = 0;
+        let expected = TritRng::new(move || {
+            n += 1;
+            n - 1
+        })
+        .t1b1(100);
+
+        assert_eq!(expected, TritRng::new(RandEntropy(Counter(0))).t1b1(100));
+    }
+
+    #[test]
+    fn uniform_distribution() {
+        let mut rng = TritRng::seeded(7);
+        let mut trits = [0usize; 3];
+        let mut trytes = [0usize; 27];
+
+        for _ in 0..30_000 {
+            trits[(rng.trit() as i8 + 1) as usize] += 1;
+        }
+        for _ in 0..27_000 {
+            trytes[(rng.tryte() as i8 + 13) as usize] += 1;
+        }
+
+        // The standard deviations of the counts are about 82 and 31, so counts more than 6 of
+        // them away from the expected ones would be a bug.
+        assert!(trits.iter().all(|n| (9_500..=10_500).contains(n)));
+        assert!(trytes.iter().all(|n| (800..=1_200).contains(n)));
+    }
+}
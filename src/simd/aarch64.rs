@@ -0,0 +1,87 @@
+//! NEON backend.
+
+pub(crate) mod neon {
+    use std::arch::aarch64::*;
+
+    type V = int8x16_t;
+
+    const LANES: usize = 1;
+
+    #[target_feature(enable = "neon")]
+    unsafe fn load(p: *const i8, _stride: usize) -> V {
+        vld1q_s8(p)
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn store(p: *mut i8, _stride: usize, v: V) {
+        vst1q_s8(p, v)
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn mask(indices: &[i8; 16]) -> V {
+        vld1q_s8(indices.as_ptr())
+    }
+
+    /// `tbl` yields zero for every index of 16 or above, which covers the `-1` entries.
+    #[target_feature(enable = "neon")]
+    unsafe fn shuffle(v: V, mask: V) -> V {
+        vqtbl1q_s8(v, vreinterpretq_u8_s8(mask))
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn splat(x: i8) -> V {
+        vdupq_n_s8(x)
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn add(a: V, b: V) -> V {
+        vaddq_s8(a, b)
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn sub(a: V, b: V) -> V {
+        vsubq_s8(a, b)
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn subs_u(a: V, b: V) -> V {
+        vreinterpretq_s8_u8(vqsubq_u8(vreinterpretq_u8_s8(a), vreinterpretq_u8_s8(b)))
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn min_u(a: V, b: V) -> V {
+        vreinterpretq_s8_u8(vminq_u8(vreinterpretq_u8_s8(a), vreinterpretq_u8_s8(b)))
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn and(a: V, b: V) -> V {
+        vandq_s8(a, b)
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn andnot(mask: V, b: V) -> V {
+        vbicq_s8(b, mask)
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn or(a: V, b: V) -> V {
+        vorrq_s8(a, b)
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn eq(a: V, b: V) -> V {
+        vreinterpretq_s8_u8(vceqq_s8(a, b))
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn gt(a: V, b: V) -> V {
+        vreinterpretq_s8_u8(vcgtq_s8(a, b))
+    }
+
+    #[target_feature(enable = "neon")]
+    unsafe fn all(mask: V) -> bool {
+        vminvq_u8(vreinterpretq_u8_s8(mask)) == 0xff
+    }
+
+    crate::simd::kernels::kernels!("neon");
+}
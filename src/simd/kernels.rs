@@ -0,0 +1,222 @@
+//! Conversion kernels shared by all vector backends.
+//!
+//! Every backend module defines a vector type `V` holding `LANES` independent 16 byte blocks and
+//! the primitives used below, then invokes `kernels!` with the target feature to compile for.
+//! Shuffles follow the `pshufb`/`tbl` convention: an index with the high bit set yields zero.
+
+/// Shuffle indices gathering every `N`th byte (starting at byte `j`) of `N` consecutive 16 byte
+/// blocks into a single block: `GATHER[j][m]` selects from block `m`.
+pub(crate) const fn gather<const N: usize>() -> [[[i8; 16]; N]; N] {
+    let mut masks = [[[-1i8; 16]; N]; N];
+    let mut j = 0;
+
+    while j < N {
+        let mut k = 0;
+        while k < 16 {
+            let index = N * k + j;
+            masks[j][index / 16][k] = (index % 16) as i8;
+            k += 1;
+        }
+        j += 1;
+    }
+
+    masks
+}
+
+/// Shuffle indices scattering `N` blocks back into `N` consecutive interleaved blocks:
+/// `SCATTER[m][j]` selects from block `j` for output block `m`.
+pub(crate) const fn scatter<const N: usize>() -> [[[i8; 16]; N]; N] {
+    let mut masks = [[[-1i8; 16]; N]; N];
+    let mut m = 0;
+
+    while m < N {
+        let mut p = 0;
+        while p < 16 {
+            let index = 16 * m + p;
+            masks[m][index % N][p] = (index / N) as i8;
+            p += 1;
+        }
+        m += 1;
+    }
+
+    masks
+}
+
+pub(crate) const GATHER3: [[[i8; 16]; 3]; 3] = gather::<3>();
+pub(crate) const GATHER5: [[[i8; 16]; 5]; 5] = gather::<5>();
+pub(crate) const SCATTER3: [[[i8; 16]; 3]; 3] = scatter::<3>();
+pub(crate) const SCATTER5: [[[i8; 16]; 5]; 5] = scatter::<5>();
+
+macro_rules! kernels {
+    ($feature:literal) => {
+        use crate::simd::kernels::{GATHER3, GATHER5, SCATTER3, SCATTER5};
+
+        #[target_feature(enable = $feature)]
+        unsafe fn gather_block<const N: usize>(x: &[V; N], masks: &[[i8; 16]; N]) -> V {
+            let mut acc = splat(0);
+            for m in 0..N {
+                acc = or(acc, shuffle(x[m], mask(&masks[m])));
+            }
+            acc
+        }
+
+        #[target_feature(enable = $feature)]
+        unsafe fn mul3(x: V) -> V {
+            add(add(x, x), x)
+        }
+
+        /// Returns `-1` in every byte where the unsigned value of `x` is at least `bound`.
+        #[target_feature(enable = $feature)]
+        unsafe fn at_least(x: V, bound: i8) -> V {
+            eq(subs_u(splat(bound), x), splat(0))
+        }
+
+        /// Splits off the balanced trit of weight `w` from the unbalanced digits in `u`.
+        #[target_feature(enable = $feature)]
+        unsafe fn split_digit(u: &mut V, w: i8) -> V {
+            let ge1 = at_least(*u, w);
+            let ge2 = at_least(*u, w.wrapping_mul(2));
+            *u = sub(sub(*u, and(ge1, splat(w))), and(ge2, splat(w)));
+            sub(sub(splat(-1), ge1), ge2)
+        }
+
+        /// Converts `3 * 16 * LANES` trits per iteration into trytes.
+        #[target_feature(enable = $feature)]
+        pub(crate) unsafe fn t1b1_to_t3b1(src: &[i8], dst: &mut [i8]) -> usize {
+            let block = 16 * LANES;
+            let n = dst.len().min(src.len() / 3) / block * block;
+            let mut i = 0;
+
+            while i < n {
+                let s = src.as_ptr().add(i * 3);
+                let x = [load(s, 48), load(s.add(16), 48), load(s.add(32), 48)];
+
+                let a = gather_block(&x, &GATHER3[0]);
+                let b = gather_block(&x, &GATHER3[1]);
+                let c = gather_block(&x, &GATHER3[2]);
+
+                store(dst.as_mut_ptr().add(i), 16, add(a, mul3(add(b, mul3(c)))));
+                i += block;
+            }
+
+            n
+        }
+
+        /// Converts `16 * LANES` trytes per iteration into trits.
+        #[target_feature(enable = $feature)]
+        pub(crate) unsafe fn t3b1_to_t1b1(src: &[i8], dst: &mut [i8]) -> usize {
+            let block = 16 * LANES;
+            let n = src.len().min(dst.len() / 3) / block * block;
+            let mut i = 0;
+
+            while i < n {
+                let mut u = add(load(src.as_ptr().add(i), 16), splat(13));
+                let c = split_digit(&mut u, 9);
+                let b = split_digit(&mut u, 3);
+                let a = sub(u, splat(1));
+                let x = [a, b, c];
+
+                let d = dst.as_mut_ptr().add(i * 3);
+                store(d, 48, gather_block(&x, &SCATTER3[0]));
+                store(d.add(16), 48, gather_block(&x, &SCATTER3[1]));
+                store(d.add(32), 48, gather_block(&x, &SCATTER3[2]));
+                i += block;
+            }
+
+            n
+        }
+
+        /// Packs `5 * 16 * LANES` trits per iteration into T5B1 bytes.
+        #[target_feature(enable = $feature)]
+        pub(crate) unsafe fn t1b1_to_t5b1(src: &[i8], dst: &mut [u8]) -> usize {
+            let block = 16 * LANES;
+            let n = dst.len().min(src.len() / 5) / block * block;
+            let mut i = 0;
+
+            while i < n {
+                let s = src.as_ptr().add(i * 5);
+                let x = [
+                    load(s, 80),
+                    load(s.add(16), 80),
+                    load(s.add(32), 80),
+                    load(s.add(48), 80),
+                    load(s.add(64), 80),
+                ];
+
+                let mut v = gather_block(&x, &GATHER5[4]);
+                for j in (0..4).rev() {
+                    v = add(gather_block(&x, &GATHER5[j]), mul3(v));
+                }
+
+                // Negative values are stored as `v + 243`, which is `v - 13` modulo 256.
+                let negative = gt(splat(0), v);
+                v = add(v, and(negative, splat(-13)));
+
+                store(dst.as_mut_ptr().add(i) as *mut i8, 16, v);
+                i += block;
+            }
+
+            n
+        }
+
+        /// Unpacks `16 * LANES` T5B1 bytes per iteration into trits. Stops in front of the first
+        /// block containing a byte above 242, so the caller can report it.
+        #[target_feature(enable = $feature)]
+        pub(crate) unsafe fn t5b1_to_t1b1(src: &[u8], dst: &mut [i8]) -> usize {
+            let block = 16 * LANES;
+            let n = src.len().min(dst.len() / 5) / block * block;
+            let mut i = 0;
+
+            while i < n {
+                let bytes = load(src.as_ptr().add(i) as *const i8, 16);
+                if !all(eq(subs_u(bytes, splat(242u8 as i8)), splat(0))) {
+                    break;
+                }
+
+                // Shift into unbalanced base 3: `byte + 121` for 0..=121, `byte - 122` above.
+                let low = eq(min_u(bytes, splat(121)), bytes);
+                let mut u = add(bytes, or(and(low, splat(121)), andnot(low, splat(-122))));
+
+                let t4 = split_digit(&mut u, 81);
+                let t3 = split_digit(&mut u, 27);
+                let t2 = split_digit(&mut u, 9);
+                let t1 = split_digit(&mut u, 3);
+                let t0 = sub(u, splat(1));
+                let x = [t0, t1, t2, t3, t4];
+
+                let d = dst.as_mut_ptr().add(i * 5);
+                for m in 0..5 {
+                    store(d.add(16 * m), 80, gather_block(&x, &SCATTER5[m]));
+                }
+                i += block;
+            }
+
+            i
+        }
+
+        /// Returns the number of leading bytes checked to be tryte characters (`9`, `A`..=`Z`),
+        /// in multiples of `16 * LANES`.
+        #[target_feature(enable = $feature)]
+        pub(crate) unsafe fn valid_tryte_prefix(src: &[u8]) -> usize {
+            let block = 16 * LANES;
+            let n = src.len() / block * block;
+            let mut i = 0;
+
+            while i < n {
+                let bytes = load(src.as_ptr().add(i) as *const i8, 16);
+                let letter = sub(bytes, splat(b'A' as i8));
+                let is_letter = eq(min_u(letter, splat(25)), letter);
+                let is_nine = eq(bytes, splat(b'9' as i8));
+
+                if !all(or(is_letter, is_nine)) {
+                    break;
+                }
+                i += block;
+            }
+
+            i
+        }
+    };
+}
+
+pub(crate) use kernels;
@@ -0,0 +1,236 @@
+//! Vectorized bulk conversions.
+//!
+//! Each function converts as many whole blocks as the best instruction set detected at runtime
+//! allows and returns how many output trytes/bytes (or consumed input bytes) it produced. The
+//! caller finishes the remainder with its scalar code, which is also the fallback on targets
+//! without a vector backend.
+//!
+//! This is the only place with `unsafe` code, for two reasons:
+//!     * Intrinsics and `#[target_feature]` functions are `unsafe` to call. Every call goes
+//!       through `dispatch!`, which only picks a backend after detecting its features at
+//!       runtime, and the kernels bound all loads and stores by the slice lengths.
+//!     * `BalancedTrit` and `BalancedTryte` are `repr(i8)`, so their slices are reinterpreted as
+//!       `&[i8]` for the kernels. Writing back is only done with values that are valid
+//!       discriminants, see the `SAFETY` comments below.
+//!
+//! The tests compare every kernel with a scalar reference for lengths around the block sizes.
+
+mod kernels;
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64;
+#[cfg(target_arch = "x86_64")]
+mod x86;
+
+use crate::trit::BalancedTrit;
+use crate::tryte::BalancedTryte;
+
+use std::slice;
+
+macro_rules! dispatch {
+    ($kernel:ident($($arg:expr),*)) => {{
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx2") {
+                return unsafe { x86::avx2::$kernel($($arg),*) };
+            }
+            if is_x86_feature_detected!("sse4.1") {
+                return unsafe { x86::sse41::$kernel($($arg),*) };
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return unsafe { aarch64::neon::$kernel($($arg),*) };
+            }
+        }
+        0
+    }};
+}
+
+fn trits_as_i8(trits: &[BalancedTrit]) -> &[i8] {
+    // SAFETY: `BalancedTrit` is `repr(i8)`.
+    unsafe { slice::from_raw_parts(trits.as_ptr() as *const i8, trits.len()) }
+}
+
+fn trytes_as_i8(trytes: &[BalancedTryte]) -> &[i8] {
+    // SAFETY: `BalancedTryte` is `repr(i8)`.
+    unsafe { slice::from_raw_parts(trytes.as_ptr() as *const i8, trytes.len()) }
+}
+
+/// SAFETY: the caller must only write valid `BalancedTrit` discriminants.
+unsafe fn trits_as_i8_mut(trits: &mut [BalancedTrit]) -> &mut [i8] {
+    slice::from_raw_parts_mut(trits.as_mut_ptr() as *mut i8, trits.len())
+}
+
+/// SAFETY: the caller must only write valid `BalancedTryte` discriminants.
+unsafe fn trytes_as_i8_mut(trytes: &mut [BalancedTryte]) -> &mut [i8] {
+    slice::from_raw_parts_mut(trytes.as_mut_ptr() as *mut i8, trytes.len())
+}
+
+/// Fills a prefix of `trytes` from `trits` and returns its length.
+pub(crate) fn t1b1_to_t3b1(trits: &[BalancedTrit], trytes: &mut [BalancedTryte]) -> usize {
+    let src = trits_as_i8(trits);
+    // SAFETY: the sum of three valid trits is a valid tryte.
+    let dst = unsafe { trytes_as_i8_mut(trytes) };
+    dispatch!(t1b1_to_t3b1(src, dst))
+}
+
+/// Converts a prefix of `trytes` into `trits` and returns its length in trytes.
+pub(crate) fn t3b1_to_t1b1(trytes: &[BalancedTryte], trits: &mut [BalancedTrit]) -> usize {
+    let src = trytes_as_i8(trytes);
+    // SAFETY: the kernels only produce -1, 0 and 1 for valid trytes.
+    let dst = unsafe { trits_as_i8_mut(trits) };
+    dispatch!(t3b1_to_t1b1(src, dst))
+}
+
+/// Fills a prefix of `bytes` from `trits` and returns its length.
+pub(crate) fn t1b1_to_t5b1(trits: &[BalancedTrit], bytes: &mut [u8]) -> usize {
+    let src = trits_as_i8(trits);
+    dispatch!(t1b1_to_t5b1(src, bytes))
+}
+
+/// Converts a prefix of `bytes` into `trits` and returns its length in bytes. Blocks containing
+/// invalid bytes are left to the caller.
+///
+/// The kernels decode arithmetically and never touch the lookup table, so the
+/// `t5b1-arith-decode` feature only selects how the scalar remainder is decoded.
+pub(crate) fn t5b1_to_t1b1(bytes: &[u8], trits: &mut [BalancedTrit]) -> usize {
+    // SAFETY: the kernels only produce -1, 0 and 1 for bytes in 0..=242 and skip all others.
+    let dst = unsafe { trits_as_i8_mut(trits) };
+    dispatch!(t5b1_to_t1b1(bytes, dst))
+}
+
+/// Returns the length of a prefix of `bytes` that only contains tryte characters.
+pub(crate) fn valid_tryte_prefix(bytes: &[u8]) -> usize {
+    dispatch!(valid_tryte_prefix(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::{SplitMix64, TritRng};
+
+    const LENGTHS: [usize; 7] = [0, 1, 15, 16, 33, 257, 1000];
+
+    fn random_trits(rng: &mut TritRng<SplitMix64>, n: usize) -> Vec<i8> {
+        (0..n).map(|_| rng.trit() as i8).collect()
+    }
+
+    fn random_trytes(rng: &mut TritRng<SplitMix64>, n: usize) -> Vec<i8> {
+        (0..n).map(|_| rng.tryte() as i8).collect()
+    }
+
+    fn ref_t1b1_to_t3b1(src: &[i8]) -> Vec<i8> {
+        src.chunks(3).map(|t| t[0] + t[1] * 3 + t[2] * 9).collect()
+    }
+
+    fn ref_t3b1_to_t1b1(src: &[i8]) -> Vec<i8> {
+        src.iter()
+            .flat_map(|t| BalancedTryte::from(*t).as_trits().to_vec())
+            .collect()
+    }
+
+    fn ref_t1b1_to_t5b1(src: &[i8]) -> Vec<u8> {
+        src.chunks(5)
+            .map(|t| {
+                let v = t[0] as i16 + t[1] as i16 * 3 + t[2] as i16 * 9 + t[3] as i16 * 27;
+                let v = v + t[4] as i16 * 81;
+                if v < 0 {
+                    (v + 243) as u8
+                } else {
+                    v as u8
+                }
+            })
+            .collect()
+    }
+
+    fn ref_t5b1_to_t1b1(src: &[u8]) -> Vec<i8> {
+        src.iter()
+            .flat_map(|b| {
+                let mut v = (*b as i16 + 121) % 243;
+                (0..5).map(move |_| {
+                    let trit = (v % 3) as i8 - 1;
+                    v /= 3;
+                    trit
+                })
+            })
+            .collect()
+    }
+
+    /// Checks a single backend against the scalar reference. Backends may leave a tail of less
+    /// than two blocks to the caller.
+    macro_rules! check_backend {
+        ($backend:path) => {{
+            use $backend as backend;
+            let mut rng = TritRng::seeded(0x5eed);
+
+            for n in LENGTHS.iter().copied() {
+                let trits = random_trits(&mut rng, n * 3);
+                let mut trytes = vec![0i8; n];
+                let done = unsafe { backend::t1b1_to_t3b1(&trits, &mut trytes) };
+                assert!(n - done < 32);
+                assert_eq!(ref_t1b1_to_t3b1(&trits[..done * 3]), &trytes[..done]);
+
+                let trytes = random_trytes(&mut rng, n);
+                let mut trits = vec![0i8; n * 3];
+                let done = unsafe { backend::t3b1_to_t1b1(&trytes, &mut trits) };
+                assert!(n - done < 32);
+                assert_eq!(ref_t3b1_to_t1b1(&trytes[..done]), &trits[..done * 3]);
+
+                let trits = random_trits(&mut rng, n * 5);
+                let mut bytes = vec![0u8; n];
+                let done = unsafe { backend::t1b1_to_t5b1(&trits, &mut bytes) };
+                assert!(n - done < 32);
+                assert_eq!(ref_t1b1_to_t5b1(&trits[..done * 5]), &bytes[..done]);
+
+                let bytes = rng.t5b1(n).into_bytes();
+                let mut trits = vec![0i8; n * 5];
+                let done = unsafe { backend::t5b1_to_t1b1(&bytes, &mut trits) };
+                assert!(n - done < 32);
+                assert_eq!(ref_t5b1_to_t1b1(&bytes[..done]), &trits[..done * 5]);
+            }
+
+            let mut bytes = (0..=242).collect::<Vec<u8>>();
+            bytes.resize(256, 0);
+            let mut trits = vec![0i8; 256 * 5];
+            assert_eq!(256, unsafe { backend::t5b1_to_t1b1(&bytes, &mut trits) });
+            assert_eq!(ref_t5b1_to_t1b1(&bytes), trits);
+
+            bytes[100] = 243;
+            assert_eq!(96, unsafe { backend::t5b1_to_t1b1(&bytes, &mut trits) });
+
+            let mut trytes = b"9ABCDEFGHIJKLMNOPQRSTUVWXYZ9ABCDEFGHIJKLMNOPQRSTUVWXYZ".to_vec();
+            let valid = unsafe { backend::valid_tryte_prefix(&trytes) };
+            assert!(trytes.len() - valid < 32);
+            for invalid in b"8:@[a ".iter() {
+                trytes[20] = *invalid;
+                assert!(unsafe { backend::valid_tryte_prefix(&trytes) } <= 20);
+            }
+        }};
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn sse41_matches_scalar() {
+        if is_x86_feature_detected!("sse4.1") {
+            check_backend!(x86::sse41);
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn avx2_matches_scalar() {
+        if is_x86_feature_detected!("avx2") {
+            check_backend!(x86::avx2);
+        }
+    }
+
+    #[test]
+    #[cfg(target_arch = "aarch64")]
+    fn neon_matches_scalar() {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            check_backend!(aarch64::neon);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! SSE4.1 and AVX2 backends.
+
+pub(crate) mod sse41 {
+    use std::arch::x86_64::*;
+
+    type V = __m128i;
+
+    const LANES: usize = 1;
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn load(p: *const i8, _stride: usize) -> V {
+        _mm_loadu_si128(p as *const V)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn store(p: *mut i8, _stride: usize, v: V) {
+        _mm_storeu_si128(p as *mut V, v)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn mask(indices: &[i8; 16]) -> V {
+        _mm_loadu_si128(indices.as_ptr() as *const V)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn shuffle(v: V, mask: V) -> V {
+        _mm_shuffle_epi8(v, mask)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn splat(x: i8) -> V {
+        _mm_set1_epi8(x)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn add(a: V, b: V) -> V {
+        _mm_add_epi8(a, b)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn sub(a: V, b: V) -> V {
+        _mm_sub_epi8(a, b)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn subs_u(a: V, b: V) -> V {
+        _mm_subs_epu8(a, b)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn min_u(a: V, b: V) -> V {
+        _mm_min_epu8(a, b)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn and(a: V, b: V) -> V {
+        _mm_and_si128(a, b)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn andnot(mask: V, b: V) -> V {
+        _mm_andnot_si128(mask, b)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn or(a: V, b: V) -> V {
+        _mm_or_si128(a, b)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn eq(a: V, b: V) -> V {
+        _mm_cmpeq_epi8(a, b)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn gt(a: V, b: V) -> V {
+        _mm_cmpgt_epi8(a, b)
+    }
+
+    #[target_feature(enable = "sse4.1")]
+    unsafe fn all(mask: V) -> bool {
+        _mm_movemask_epi8(mask) == 0xffff
+    }
+
+    crate::simd::kernels::kernels!("sse4.1");
+}
+
+pub(crate) mod avx2 {
+    use std::arch::x86_64::*;
+
+    type V = __m256i;
+
+    /// Each 128 bit lane processes its own block, since AVX2 shuffles don't cross lanes.
+    const LANES: usize = 2;
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn load(p: *const i8, stride: usize) -> V {
+        _mm256_loadu2_m128i(p.add(stride) as *const __m128i, p as *const __m128i)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn store(p: *mut i8, stride: usize, v: V) {
+        _mm256_storeu2_m128i(p.add(stride) as *mut __m128i, p as *mut __m128i, v)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn mask(indices: &[i8; 16]) -> V {
+        _mm256_broadcastsi128_si256(_mm_loadu_si128(indices.as_ptr() as *const __m128i))
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn shuffle(v: V, mask: V) -> V {
+        _mm256_shuffle_epi8(v, mask)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn splat(x: i8) -> V {
+        _mm256_set1_epi8(x)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn add(a: V, b: V) -> V {
+        _mm256_add_epi8(a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn sub(a: V, b: V) -> V {
+        _mm256_sub_epi8(a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn subs_u(a: V, b: V) -> V {
+        _mm256_subs_epu8(a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn min_u(a: V, b: V) -> V {
+        _mm256_min_epu8(a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn and(a: V, b: V) -> V {
+        _mm256_and_si256(a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn andnot(mask: V, b: V) -> V {
+        _mm256_andnot_si256(mask, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn or(a: V, b: V) -> V {
+        _mm256_or_si256(a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn eq(a: V, b: V) -> V {
+        _mm256_cmpeq_epi8(a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn gt(a: V, b: V) -> V {
+        _mm256_cmpgt_epi8(a, b)
+    }
+
+    #[target_feature(enable = "avx2")]
+    unsafe fn all(mask: V) -> bool {
+        _mm256_movemask_epi8(mask) == -1
+    }
+
+    crate::simd::kernels::kernels!("avx2");
+}
@@ -0,0 +1,164 @@
+//! Hex and base64 (RFC 4648, with padding) representations of raw bytes.
+
+use crate::error::Error;
+
+use std::fmt;
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub(crate) fn write_hex(f: &mut fmt::Formatter, bytes: &[u8], upper: bool) -> fmt::Result {
+    for byte in bytes {
+        if upper {
+            write!(f, "{:02X}", byte)?;
+        } else {
+            write!(f, "{:02x}", byte)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len() * 2);
+
+    for byte in bytes {
+        s.push_str(&format!("{:02x}", byte));
+    }
+
+    s
+}
+
+/// Accepts upper and lower case digits.
+pub(crate) fn from_hex(s: &str) -> Result<Vec<u8>, Error> {
+    let digits = s.as_bytes();
+    if digits.len() % 2 == 1 {
+        return Err(Error::InvalidHex {
+            offset: digits.len(),
+        });
+    }
+
+    let mut bytes = Vec::with_capacity(digits.len() / 2);
+
+    for (i, pair) in digits.chunks(2).enumerate() {
+        let hi = hex_digit(pair[0]).ok_or(Error::InvalidHex { offset: i * 2 })?;
+        let lo = hex_digit(pair[1]).ok_or(Error::InvalidHex { offset: i * 2 + 1 })?;
+        bytes.push(hi << 4 | lo);
+    }
+
+    Ok(bytes)
+}
+
+pub(crate) fn to_base64(bytes: &[u8]) -> String {
+    let mut s = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                s.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                s.push('=');
+            }
+        }
+    }
+
+    s
+}
+
+/// Requires canonical padding, i.e. a length that is a multiple of 4.
+#[allow(clippy::manual_is_multiple_of)]
+pub(crate) fn from_base64(s: &str) -> Result<Vec<u8>, Error> {
+    let chars = s.as_bytes();
+    if chars.len() % 4 != 0 {
+        return Err(Error::InvalidBase64 {
+            offset: chars.len(),
+        });
+    }
+
+    let mut bytes = Vec::with_capacity(chars.len() / 4 * 3);
+
+    for (i, chunk) in chars.chunks(4).enumerate() {
+        let last = (i + 1) * 4 == chars.len();
+        let padding = chunk.iter().rev().take_while(|c| **c == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return Err(Error::InvalidBase64 {
+                offset: i * 4 + 4 - padding,
+            });
+        }
+
+        let mut n = 0u32;
+        for (j, c) in chunk[..4 - padding].iter().enumerate() {
+            let value = base64_digit(*c).ok_or(Error::InvalidBase64 { offset: i * 4 + j })?;
+            n |= (value as u32) << (18 - 6 * j);
+        }
+
+        let decoded = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        bytes.extend_from_slice(&decoded[..3 - padding]);
+    }
+
+    Ok(bytes)
+}
+
+fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn base64_digit(c: u8) -> Option<u8> {
+    BASE64_ALPHABET
+        .iter()
+        .position(|d| *d == c)
+        .map(|i| i as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trip() {
+        assert_eq!("", to_hex(&[]));
+        assert_eq!("00ff7a", to_hex(&[0, 255, 122]));
+        assert_eq!(Ok(vec![0, 255, 122]), from_hex("00FF7a"));
+        assert_eq!(Err(Error::InvalidHex { offset: 3 }), from_hex("00fg"));
+        assert_eq!(Err(Error::InvalidHex { offset: 3 }), from_hex("00f"));
+    }
+
+    #[test]
+    fn base64_rfc4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+
+        for (plain, encoded) in vectors.iter() {
+            assert_eq!(*encoded, to_base64(plain.as_bytes()));
+            assert_eq!(Ok(plain.as_bytes().to_vec()), from_base64(encoded));
+        }
+    }
+
+    #[test]
+    fn base64_errors() {
+        assert_eq!(Err(Error::InvalidBase64 { offset: 3 }), from_base64("Zm9"));
+        assert_eq!(Err(Error::InvalidBase64 { offset: 2 }), from_base64("Zm-v"));
+        assert_eq!(
+            Err(Error::InvalidBase64 { offset: 2 }),
+            from_base64("Zg==Zm9v")
+        );
+    }
+}
@@ -1,9 +1,11 @@
 use std::fmt;
 
+/// Trits are ordered by their value, i.e. `-` < `0` < `1`.
 #[repr(i8)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum BalancedTrit {
     MinusOne = -1,
+    #[default]
     Zero = 0,
     PlusOne = 1,
 }
@@ -62,6 +64,22 @@ mod tests {
         assert_eq!(BalancedTrit::MinusOne, (-1).into());
     }
 
+    #[test]
+    fn order_trits_by_value() {
+        let mut trits = [
+            BalancedTrit::PlusOne,
+            BalancedTrit::MinusOne,
+            BalancedTrit::Zero,
+        ];
+        trits.sort();
+
+        assert_eq!(
+            "-01",
+            trits.iter().map(|t| t.to_string()).collect::<String>()
+        );
+        assert_eq!(BalancedTrit::Zero, BalancedTrit::default());
+    }
+
     #[test]
     #[should_panic]
     fn fail_for_invalid_char() {
@@ -0,0 +1,650 @@
+//! Arbitrary-precision balanced ternary integers.
+//!
+//! A `TritInt` is stored as `T5B1`, least significant byte first and without trailing zero
+//! bytes. The five trits of a byte are exactly one balanced base 243 digit in -121..=121, so
+//! arithmetic works on whole bytes instead of single trits.
+
+use crate::encodings::{t1b1::T1B1, t3b1::T3B1, t5b1::T5B1, Encoding};
+use crate::error::Error;
+use crate::trit::BalancedTrit;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Shl, Shr, Sub};
+use std::str::FromStr;
+
+const RADIX: i64 = 243;
+const HALF: i64 = 121;
+/// Products of operands with at least this many digits are computed with Karatsuba.
+const KARATSUBA_THRESHOLD: usize = 32;
+/// Decimal digits per chunk when converting from and to decimal strings.
+const DECIMAL_CHUNK: usize = 9;
+const DECIMAL_BASE: i64 = 1_000_000_000;
+
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct TritInt(T5B1);
+
+impl TritInt {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn one() -> Self {
+        Self::from(1)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns -1, 0 or 1.
+    pub fn signum(&self) -> i8 {
+        match self.digits().last() {
+            None => 0,
+            Some(d) if *d > 0 => 1,
+            Some(_) => -1,
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.signum() < 0 {
+            -self
+        } else {
+            self.clone()
+        }
+    }
+
+    /// The number of trits without leading zeros.
+    pub fn trit_len(&self) -> usize {
+        self.to_t1b1().len()
+    }
+
+    /// Returns the quotient and the remainder with the smallest absolute value, i.e.
+    /// `self == q * divisor + r` with `-|divisor| < 2r <= |divisor|`.
+    ///
+    /// NOTE: panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        if divisor.is_zero() {
+            panic!("Division by zero");
+        }
+
+        let d = divisor.abs();
+        let d3 = &d + &(&d + &d);
+        let mut q = Self::zero();
+        let mut r = Self::zero();
+
+        // Long division, most significant trit first. Before every step |r| <= |d| / 2, so the
+        // next quotient digit is in -2..=2 and simply carries into the higher trits.
+        for trit in self.to_t1b1().trits().rev() {
+            r = &(&r << 1) + &Self::from(trit as i64);
+            let r2 = &r + &r;
+
+            let digit = if r2 > d3 {
+                2
+            } else if r2 > d {
+                1
+            } else if r2 < -&d3 {
+                -2
+            } else if r2 <= -&d {
+                -1
+            } else {
+                0
+            };
+
+            q = &(&q << 1) + &Self::from(digit);
+            r = &r - &(&d * &Self::from(digit));
+        }
+
+        if divisor.signum() < 0 {
+            q = -q;
+        }
+
+        (q, r)
+    }
+
+    pub fn pow(&self, mut exp: u32) -> Self {
+        let mut base = self.clone();
+        let mut result = Self::one();
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+
+        result
+    }
+
+    /// Returns `None` if the value doesn't fit.
+    pub fn to_i64(&self) -> Option<i64> {
+        let mut value = 0i128;
+
+        for d in self.digits().iter().rev() {
+            value = value.checked_mul(RADIX as i128)?.checked_add(*d as i128)?;
+        }
+
+        i64::try_from(value).ok()
+    }
+
+    /// The minimal trits, least significant first; zero has no trits.
+    pub fn to_t1b1(&self) -> T1B1 {
+        let trits: T1B1 = self.0.trits().collect();
+        let len = trits
+            .trits()
+            .rposition(|t| t != BalancedTrit::Zero)
+            .map_or(0, |i| i + 1);
+
+        trits.trits().take(len).collect()
+    }
+
+    /// The minimal trytes, least significant first; zero has no trytes.
+    pub fn to_t3b1(&self) -> T3B1 {
+        self.to_t1b1().trits().collect()
+    }
+
+    pub fn as_t5b1(&self) -> &T5B1 {
+        &self.0
+    }
+
+    fn digits(&self) -> Vec<i64> {
+        self.0
+            .as_bytes()
+            .iter()
+            .map(|b| {
+                let d = *b as i64;
+                if d > HALF {
+                    d - RADIX
+                } else {
+                    d
+                }
+            })
+            .collect()
+    }
+
+    /// Carries every digit into -121..=121 and drops leading zero digits.
+    fn from_digits(mut digits: Vec<i64>) -> Self {
+        let mut carry = 0;
+
+        for d in digits.iter_mut() {
+            let value = *d + carry;
+            *d = balanced_rem(value);
+            carry = (value - *d) / RADIX;
+        }
+        while carry != 0 {
+            let d = balanced_rem(carry);
+            digits.push(d);
+            carry = (carry - d) / RADIX;
+        }
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+
+        let bytes = digits
+            .iter()
+            .map(|d| if *d < 0 { d + RADIX } else { *d } as u8)
+            .collect();
+
+        Self(T5B1::from_vec(bytes).expect("balanced digits are valid T5B1 bytes"))
+    }
+
+    fn normalized(self) -> Self {
+        Self::from_digits(self.digits())
+    }
+}
+
+fn balanced_rem(value: i64) -> i64 {
+    (value + HALF).rem_euclid(RADIX) - HALF
+}
+
+fn add_digits(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = long.to_vec();
+
+    for (s, d) in sum.iter_mut().zip(short) {
+        *s += d;
+    }
+
+    sum
+}
+
+/// Multiplies without carrying, so the result digits may leave -121..=121.
+fn mul_digits(a: &[i64], b: &[i64]) -> Vec<i64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return mul_schoolbook(a, b);
+    }
+
+    // a * b = z2 * R^2m + z1 * R^m + z0, with z1 = (a0 + a1) * (b0 + b1) - z2 - z0
+    let m = a.len().min(b.len()) / 2;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+
+    let z0 = mul_digits(a0, b0);
+    let z2 = mul_digits(a1, b1);
+    let z1 = mul_digits(&add_digits(a0, a1), &add_digits(b0, b1));
+
+    let mut product = vec![0; a.len() + b.len() - 1];
+    for (i, d) in z0.iter().enumerate() {
+        product[i] += d;
+        product[i + m] -= d;
+    }
+    for (i, d) in z2.iter().enumerate() {
+        product[i + 2 * m] += d;
+        product[i + m] -= d;
+    }
+    for (i, d) in z1.iter().enumerate() {
+        product[i + m] += d;
+    }
+
+    product
+}
+
+fn mul_schoolbook(a: &[i64], b: &[i64]) -> Vec<i64> {
+    let mut product = vec![0; a.len() + b.len() - 1];
+
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            product[i + j] += x * y;
+        }
+    }
+
+    product
+}
+
+impl From<i64> for TritInt {
+    fn from(value: i64) -> Self {
+        // Split in i128, since carrying i64::MAX would overflow.
+        let mut value = value as i128;
+        let mut digits = Vec::new();
+
+        while value != 0 {
+            let d = (value + HALF as i128).rem_euclid(RADIX as i128) - HALF as i128;
+            digits.push(d as i64);
+            value = (value - d) / RADIX as i128;
+        }
+
+        Self::from_digits(digits)
+    }
+}
+
+/// Reads the trits least significant first; trailing zeros are ignored.
+impl From<T1B1> for TritInt {
+    fn from(trits: T1B1) -> Self {
+        let bytes: T5B1 = trits.trits().collect();
+        Self(bytes).normalized()
+    }
+}
+
+impl From<T3B1> for TritInt {
+    fn from(trytes: T3B1) -> Self {
+        let bytes: T5B1 = trytes.trits().collect();
+        Self(bytes).normalized()
+    }
+}
+
+impl Ord for TritInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let a = self.digits();
+        let b = other.digits();
+
+        // The leading digit outweighs all lower digits together.
+        match a.len().cmp(&b.len()) {
+            Ordering::Greater => a[a.len() - 1].cmp(&0),
+            Ordering::Less => 0.cmp(&b[b.len() - 1]),
+            Ordering::Equal => a.iter().rev().cmp(b.iter().rev()),
+        }
+    }
+}
+
+impl PartialOrd for TritInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for &TritInt {
+    type Output = TritInt;
+
+    fn neg(self) -> TritInt {
+        let bytes = self
+            .0
+            .as_bytes()
+            .iter()
+            .map(|b| if *b == 0 { 0 } else { 243 - b })
+            .collect();
+
+        TritInt(T5B1::from_vec(bytes).expect("negated bytes are valid T5B1 bytes"))
+    }
+}
+
+impl Neg for TritInt {
+    type Output = TritInt;
+
+    fn neg(self) -> TritInt {
+        -&self
+    }
+}
+
+impl Add for &TritInt {
+    type Output = TritInt;
+
+    fn add(self, rhs: &TritInt) -> TritInt {
+        TritInt::from_digits(add_digits(&self.digits(), &rhs.digits()))
+    }
+}
+
+impl Sub for &TritInt {
+    type Output = TritInt;
+
+    fn sub(self, rhs: &TritInt) -> TritInt {
+        self + &-rhs
+    }
+}
+
+impl Mul for &TritInt {
+    type Output = TritInt;
+
+    fn mul(self, rhs: &TritInt) -> TritInt {
+        TritInt::from_digits(mul_digits(&self.digits(), &rhs.digits()))
+    }
+}
+
+macro_rules! forward_owned {
+    ($($trait:ident $method:ident),*) => {
+        $(
+            impl $trait for TritInt {
+                type Output = TritInt;
+
+                fn $method(self, rhs: TritInt) -> TritInt {
+                    (&self).$method(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned!(Add add, Sub sub, Mul mul);
+
+/// Multiplies by 3^`trits`.
+impl Shl<usize> for &TritInt {
+    type Output = TritInt;
+
+    fn shl(self, trits: usize) -> TritInt {
+        if self.is_zero() {
+            return TritInt::zero();
+        }
+
+        let factor = 3i64.pow((trits % 5) as u32);
+        let mut digits = vec![0; trits / 5];
+        digits.extend(self.digits().iter().map(|d| d * factor));
+
+        TritInt::from_digits(digits)
+    }
+}
+
+/// Divides by 3^`trits`, rounding to the nearest integer, which is simply dropping the lowest
+/// trits in balanced ternary.
+impl Shr<usize> for &TritInt {
+    type Output = TritInt;
+
+    fn shr(self, trits: usize) -> TritInt {
+        self.to_t1b1().trits().skip(trits).collect::<T1B1>().into()
+    }
+}
+
+impl Shl<usize> for TritInt {
+    type Output = TritInt;
+
+    fn shl(self, trits: usize) -> TritInt {
+        &self << trits
+    }
+}
+
+impl Shr<usize> for TritInt {
+    type Output = TritInt;
+
+    fn shr(self, trits: usize) -> TritInt {
+        &self >> trits
+    }
+}
+
+/// Prints the value in decimal.
+impl fmt::Display for TritInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        if self.signum() < 0 {
+            write!(f, "-")?;
+        }
+
+        // Horner's scheme into base 10^9. Every prefix of a positive number is positive, so
+        // the carry out of the top chunk never becomes negative.
+        let mut chunks: Vec<i64> = Vec::new();
+
+        for d in self.abs().digits().iter().rev() {
+            let mut carry = *d;
+            for chunk in chunks.iter_mut() {
+                let value = *chunk * RADIX + carry;
+                *chunk = value.rem_euclid(DECIMAL_BASE);
+                carry = value.div_euclid(DECIMAL_BASE);
+            }
+            while carry > 0 {
+                chunks.push(carry % DECIMAL_BASE);
+                carry /= DECIMAL_BASE;
+            }
+        }
+
+        let mut chunks = chunks.iter().rev();
+        if let Some(top) = chunks.next() {
+            write!(f, "{}", top)?;
+        }
+        for chunk in chunks {
+            write!(f, "{:09}", chunk)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a decimal string with an optional sign.
+impl FromStr for TritInt {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let (negative, start) = match s.as_bytes().first() {
+            Some(b'-') => (true, 1),
+            Some(b'+') => (false, 1),
+            _ => (false, 0),
+        };
+
+        let decimals = &s.as_bytes()[start..];
+        if decimals.is_empty() {
+            return Err(Error::InvalidDecimal { offset: s.len() });
+        }
+        if let Some(i) = decimals.iter().position(|c| !c.is_ascii_digit()) {
+            return Err(Error::InvalidDecimal { offset: start + i });
+        }
+
+        let mut digits: Vec<i64> = Vec::new();
+
+        for chunk in decimals.chunks(DECIMAL_CHUNK) {
+            let scale = 10i64.pow(chunk.len() as u32);
+            let value = chunk
+                .iter()
+                .fold(0i64, |acc, c| acc * 10 + (c - b'0') as i64);
+
+            for d in digits.iter_mut() {
+                *d *= scale;
+            }
+            match digits.first_mut() {
+                Some(d) => *d += value,
+                None => digits.push(value),
+            }
+
+            digits = TritInt::from_digits(digits).digits();
+        }
+
+        let value = TritInt::from_digits(digits);
+        Ok(if negative { -value } else { value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::{Entropy, SplitMix64};
+
+    fn int(value: i64) -> TritInt {
+        TritInt::from(value)
+    }
+
+    fn random_int(rng: &mut SplitMix64, digits: usize) -> TritInt {
+        let digits = (0..digits)
+            .map(|_| (rng.next_u64() % 243) as i64 - HALF)
+            .collect();
+        TritInt::from_digits(digits)
+    }
+
+    #[test]
+    fn small_values() {
+        assert_eq!("", int(0).to_t1b1().to_string());
+        assert_eq!("1", int(1).to_t1b1().to_string());
+        assert_eq!("-1", int(2).to_t1b1().to_string());
+        assert_eq!("0-1", int(6).to_t1b1().to_string());
+        assert_eq!("0-0-", int(-30).to_t1b1().to_string());
+        assert_eq!(int(-30), TritInt::from(T1B1::from("0-0-0000000")));
+    }
+
+    #[test]
+    fn i64_round_trip() {
+        let mut rng = SplitMix64::new(1);
+
+        for value in [0, 1, -1, 121, 122, -122, i64::MAX, i64::MIN] {
+            assert_eq!(Some(value), int(value).to_i64());
+            assert_eq!(value.to_string(), int(value).to_string());
+        }
+        for _ in 0..1000 {
+            let value = rng.next_u64() as i64;
+            assert_eq!(Some(value), int(value).to_i64());
+        }
+        assert_eq!(None, (&int(i64::MAX) + &int(1)).to_i64());
+    }
+
+    #[test]
+    fn arithmetic_matches_i128() {
+        let mut rng = SplitMix64::new(2);
+
+        for _ in 0..1000 {
+            let a = rng.next_u64() as i64 >> (rng.next_u64() % 64);
+            let b = rng.next_u64() as i64 >> (rng.next_u64() % 64);
+            let (x, y) = (int(a), int(b));
+
+            assert_eq!((a as i128 + b as i128).to_string(), (&x + &y).to_string());
+            assert_eq!((a as i128 - b as i128).to_string(), (&x - &y).to_string());
+            assert_eq!((a as i128 * b as i128).to_string(), (&x * &y).to_string());
+            assert_eq!(a.cmp(&b), x.cmp(&y));
+            assert_eq!(-a as i128, (-&x).to_string().parse::<i128>().unwrap());
+        }
+    }
+
+    #[test]
+    fn balanced_division() {
+        let mut rng = SplitMix64::new(3);
+
+        for _ in 0..1000 {
+            let a = rng.next_u64() as i64 >> (rng.next_u64() % 64);
+            let b = (rng.next_u64() as i64 >> (rng.next_u64() % 64)).max(1);
+            let b = if rng.next_u64() & 1 == 0 { b } else { -b };
+
+            let (q, r) = int(a).div_rem(&int(b));
+            let (q, r) = (q.to_i64().unwrap() as i128, r.to_i64().unwrap() as i128);
+
+            assert_eq!(a as i128, q * b as i128 + r);
+            assert!(-(b as i128).abs() < 2 * r && 2 * r <= (b as i128).abs());
+        }
+
+        let (q, r) = int(7).div_rem(&int(2));
+        assert_eq!((Some(3), Some(1)), (q.to_i64(), r.to_i64()));
+        let (q, r) = int(5).div_rem(&int(-2));
+        assert_eq!((Some(-2), Some(1)), (q.to_i64(), r.to_i64()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_for_division_by_zero() {
+        let _ = int(1).div_rem(&int(0));
+    }
+
+    #[test]
+    fn karatsuba_matches_schoolbook() {
+        let mut rng = SplitMix64::new(4);
+
+        for (m, n) in [(32, 32), (50, 200), (129, 77), (300, 300)] {
+            let a = random_int(&mut rng, m).digits();
+            let b = random_int(&mut rng, n).digits();
+
+            assert_eq!(
+                TritInt::from_digits(mul_schoolbook(&a, &b)),
+                TritInt::from_digits(mul_digits(&a, &b))
+            );
+        }
+    }
+
+    #[test]
+    fn powers_and_shifts() {
+        let three = int(3);
+
+        assert_eq!("12157665459056928801", three.pow(40).to_string());
+        assert_eq!(&int(1) << 40, three.pow(40));
+        assert_eq!(244, three.pow(243).trit_len());
+        assert_eq!(int(1), int(1).pow(1000));
+        assert_eq!(int(1), int(5).pow(0));
+
+        assert_eq!(int(5 * 27), int(5) << 3);
+        assert_eq!(int(-5 * 243 * 3), int(-5) << 6);
+        // 14 / 9 rounds to 2, -14 / 9 to -2.
+        assert_eq!(int(2), int(14) >> 2);
+        assert_eq!(int(-2), int(-14) >> 2);
+        assert_eq!(int(0), int(4) >> 2);
+    }
+
+    #[test]
+    fn decimal_round_trip() {
+        let mut rng = SplitMix64::new(5);
+
+        for digits in [1, 10, 100] {
+            let value = random_int(&mut rng, digits);
+            assert_eq!(Ok(value.clone()), value.to_string().parse());
+        }
+
+        assert_eq!(Ok(int(-42)), "-42".parse());
+        assert_eq!(Ok(int(42)), "+0042".parse());
+        assert_eq!(
+            Err(Error::InvalidDecimal { offset: 1 }),
+            "-".parse::<TritInt>()
+        );
+        assert_eq!(
+            Err(Error::InvalidDecimal { offset: 2 }),
+            "12a".parse::<TritInt>()
+        );
+    }
+
+    #[test]
+    fn t3b1_round_trip() {
+        let trytes: T3B1 = "MINITRI".into();
+        let value = TritInt::from(trytes.clone());
+
+        assert_eq!(trytes.to_string(), value.to_t3b1().to_string());
+        assert_eq!("A", int(1).to_t3b1().to_string());
+        assert_eq!("9A", int(27).to_t3b1().to_string());
+        assert_eq!(int(27), TritInt::from(T3B1::from("9A99")));
+    }
+}
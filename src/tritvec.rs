@@ -1,11 +1,18 @@
+use crate::encodings::iter::{Trits, Trytes};
 use crate::encodings::t1b1::T1B1;
-use crate::encodings::Encoding;
+use crate::encodings::{Encoding, RawBytes};
+use crate::error::Error;
+use crate::trit::BalancedTrit;
 
-#[derive(Clone, Debug)]
+use std::fmt;
+use std::iter::FromIterator;
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct TritVec<T: Encoding> {
     encoding: T,
 }
 
+#[allow(clippy::len_without_is_empty, clippy::new_without_default)]
 impl<T: Encoding> TritVec<T> {
     pub fn new() -> Self {
         Self { encoding: T::new() }
@@ -25,4 +32,50 @@ impl<T: Encoding> TritVec<T> {
     pub fn len(&self) -> usize {
         self.encoding.len()
     }
+
+    pub fn trits(&self) -> Trits<'_> {
+        self.encoding.trits()
+    }
+
+    pub fn trytes(&self) -> Trytes<'_> {
+        self.encoding.trytes()
+    }
+}
+
+impl<T: Encoding + FromIterator<BalancedTrit>> FromIterator<BalancedTrit> for TritVec<T> {
+    fn from_iter<I: IntoIterator<Item = BalancedTrit>>(iter: I) -> Self {
+        Self {
+            encoding: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: Encoding + RawBytes> RawBytes for TritVec<T> {
+    fn raw_bytes(&self) -> Vec<u8> {
+        self.encoding.raw_bytes()
+    }
+
+    fn from_raw_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            encoding: T::from_raw_bytes(bytes)?,
+        })
+    }
+}
+
+impl<T: Encoding + fmt::Display> fmt::Display for TritVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.encoding.fmt(f)
+    }
+}
+
+impl<T: Encoding + fmt::LowerHex> fmt::LowerHex for TritVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.encoding.fmt(f)
+    }
+}
+
+impl<T: Encoding + fmt::UpperHex> fmt::UpperHex for TritVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.encoding.fmt(f)
+    }
 }
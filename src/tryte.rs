@@ -1,7 +1,11 @@
+use crate::trit::BalancedTrit;
+
 use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
 
+/// Trytes are ordered by their value, i.e. `N` < `Z` < `9` < `A` < `M`.
 #[repr(i8)]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum BalancedTryte {
     N = -13,
     O = -12,
@@ -16,6 +20,7 @@ pub enum BalancedTryte {
     X = -3,
     Y = -2,
     Z = -1,
+    #[default]
     Nine = 0,
     A = 1,
     B = 2,
@@ -33,6 +38,117 @@ pub enum BalancedTryte {
 }
 
 impl BalancedTryte {
+    /// All 27 trytes in ascending order.
+    pub const ALL: [BalancedTryte; 27] = {
+        use BalancedTryte::*;
+        [
+            N, O, P, Q, R, S, T, U, V, W, X, Y, Z, Nine, A, B, C, D, E, F, G, H, I, J, K, L, M,
+        ]
+    };
+
+    /// Expects the least significant trit first.
+    pub fn from_trits(trits: [BalancedTrit; 3]) -> Self {
+        (trits[0] as i8 + trits[1] as i8 * 3 + trits[2] as i8 * 9).into()
+    }
+
+    /// Returns the least significant trit first.
+    pub fn to_trits(&self) -> [BalancedTrit; 3] {
+        self.as_trits().map(BalancedTrit::from)
+    }
+
+    pub fn to_char(&self) -> char {
+        use BalancedTryte::*;
+        match *self {
+            N => 'N',
+            O => 'O',
+            P => 'P',
+            Q => 'Q',
+            R => 'R',
+            S => 'S',
+            T => 'T',
+            U => 'U',
+            V => 'V',
+            W => 'W',
+            X => 'X',
+            Y => 'Y',
+            Z => 'Z',
+            Nine => '9',
+            A => 'A',
+            B => 'B',
+            C => 'C',
+            D => 'D',
+            E => 'E',
+            F => 'F',
+            G => 'G',
+            H => 'H',
+            I => 'I',
+            J => 'J',
+            K => 'K',
+            L => 'L',
+            M => 'M',
+        }
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Self::checked(self as i16 + rhs as i16)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Self::checked(self as i16 - rhs as i16)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Self::checked(self as i16 * rhs as i16)
+    }
+
+    /// Wraps around modulo 27, e.g. `M + A` is `N`.
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        Self::split(self as i16 + rhs as i16).0
+    }
+
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        Self::split(self as i16 - rhs as i16).0
+    }
+
+    pub fn wrapping_mul(self, rhs: Self) -> Self {
+        Self::split(self as i16 * rhs as i16).0
+    }
+
+    /// Adds `self`, `rhs` and an incoming carry, and returns the sum together with the outgoing
+    /// carry, so that trytes can be chained into larger numbers.
+    pub fn carrying_add(self, rhs: Self, carry: BalancedTrit) -> (Self, BalancedTrit) {
+        let (sum, carry) = Self::split(self as i16 + rhs as i16 + carry as i16);
+        (sum, (carry as i8).into())
+    }
+
+    /// Subtracts `rhs` and an incoming borrow from `self`, and returns the difference together
+    /// with the outgoing borrow, i.e. `self - rhs - borrow == diff - 27 * borrow_out`, so that the
+    /// borrow can be passed on to the next more significant tryte.
+    pub fn carrying_sub(self, rhs: Self, borrow: BalancedTrit) -> (Self, BalancedTrit) {
+        let (diff, carry) = Self::split(self as i16 - rhs as i16 - borrow as i16);
+        (diff, (-carry as i8).into())
+    }
+
+    /// Returns the low and the high tryte of the full product.
+    pub fn widening_mul(self, rhs: Self) -> (Self, Self) {
+        let (low, high) = Self::split(self as i16 * rhs as i16);
+        (low, (high as i8).into())
+    }
+
+    fn checked(value: i16) -> Option<Self> {
+        if (-13..=13).contains(&value) {
+            Some((value as i8).into())
+        } else {
+            None
+        }
+    }
+
+    /// Splits the value into a tryte and a multiple of 27.
+    fn split(value: i16) -> (Self, i16) {
+        let low = (value + 13).rem_euclid(27) - 13;
+        ((low as i8).into(), (value - low) / 27)
+    }
+
     pub fn as_trits(&self) -> [i8; 3] {
         use BalancedTryte::*;
         match *self {
@@ -141,38 +257,42 @@ impl From<i8> for BalancedTryte {
 
 impl fmt::Display for BalancedTryte {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use BalancedTryte::*;
-        let c = match *self {
-            N => 'N',
-            O => 'O',
-            P => 'P',
-            Q => 'Q',
-            R => 'R',
-            S => 'S',
-            T => 'T',
-            U => 'U',
-            V => 'V',
-            W => 'W',
-            X => 'X',
-            Y => 'Y',
-            Z => 'Z',
-            Nine => '9',
-            A => 'A',
-            B => 'B',
-            C => 'C',
-            D => 'D',
-            E => 'E',
-            F => 'F',
-            G => 'G',
-            H => 'H',
-            I => 'I',
-            J => 'J',
-            K => 'K',
-            L => 'L',
-            M => 'M',
-        };
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl Neg for BalancedTryte {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        (-(self as i8)).into()
+    }
+}
+
+/// Panics on overflow, see `checked_add`, `wrapping_add` and `carrying_add`.
+impl Add for BalancedTryte {
+    type Output = Self;
 
-        write!(f, "{}", c)
+    fn add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).expect("Tryte overflow")
+    }
+}
+
+/// Panics on overflow, see `checked_sub`, `wrapping_sub` and `carrying_sub`.
+impl Sub for BalancedTryte {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).expect("Tryte overflow")
+    }
+}
+
+/// Panics on overflow, see `checked_mul`, `wrapping_mul` and `widening_mul`.
+impl Mul for BalancedTryte {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).expect("Tryte overflow")
     }
 }
 
@@ -207,6 +327,106 @@ mod tests {
         assert_eq!(BalancedTryte::Z, (-1).into());
     }
 
+    #[test]
+    fn all_trytes_in_order() {
+        let values = BalancedTryte::ALL
+            .iter()
+            .map(|t| *t as i8)
+            .collect::<Vec<i8>>();
+        assert_eq!((-13..=13).collect::<Vec<i8>>(), values);
+
+        let chars = BalancedTryte::ALL
+            .iter()
+            .map(|t| t.to_char())
+            .collect::<String>();
+        assert_eq!("NOPQRSTUVWXYZ9ABCDEFGHIJKLM", chars);
+
+        assert!(BalancedTryte::N < BalancedTryte::Nine);
+        assert_eq!(Some(&BalancedTryte::M), BalancedTryte::ALL.iter().max());
+    }
+
+    #[test]
+    fn trits_round_trip() {
+        for tryte in BalancedTryte::ALL.iter() {
+            assert_eq!(*tryte, BalancedTryte::from_trits(tryte.to_trits()));
+            assert_eq!(*tryte, tryte.to_char().into());
+        }
+
+        use BalancedTrit::*;
+        assert_eq!([MinusOne, PlusOne, Zero], BalancedTryte::B.to_trits());
+    }
+
+    #[test]
+    fn negate_trytes() {
+        for tryte in BalancedTryte::ALL.iter() {
+            assert_eq!(-(*tryte as i8), -*tryte as i8);
+        }
+        assert_eq!(BalancedTryte::N, -BalancedTryte::M);
+    }
+
+    #[test]
+    fn tryte_arithmetic() {
+        use BalancedTryte::*;
+
+        assert_eq!(C, A + B);
+        assert_eq!(Z, A - B);
+        assert_eq!(F, B * C);
+        assert_eq!(None, M.checked_add(A));
+        assert_eq!(None, E.checked_mul(C));
+        assert_eq!(N, M.wrapping_add(A));
+        assert_eq!(M, N.wrapping_sub(A));
+        // 5 * 3 = 15 = 27 - 12
+        assert_eq!(O, E.wrapping_mul(C));
+    }
+
+    #[test]
+    fn carries_match_integer_arithmetic() {
+        for a in BalancedTryte::ALL.iter() {
+            for b in BalancedTryte::ALL.iter() {
+                for carry in [-1, 0, 1] {
+                    let (sum, out) = a.carrying_add(*b, carry.into());
+                    assert_eq!(
+                        *a as i16 + *b as i16 + carry as i16,
+                        sum as i16 + out as i16 * 27
+                    );
+
+                    let (diff, out) = a.carrying_sub(*b, carry.into());
+                    assert_eq!(
+                        *a as i16 - *b as i16 - carry as i16,
+                        diff as i16 - out as i16 * 27
+                    );
+                }
+
+                let (low, high) = a.widening_mul(*b);
+                assert_eq!(*a as i16 * *b as i16, low as i16 + high as i16 * 27);
+            }
+        }
+    }
+
+    #[test]
+    fn borrows_chain_across_trytes() {
+        let trytes = |value: i16| {
+            let (low, high) = BalancedTryte::split(value);
+            (low, BalancedTryte::from(high as i8))
+        };
+
+        for a in (-364..=364).step_by(7) {
+            for b in (-364..=364).step_by(11) {
+                let ((a_low, a_high), (b_low, b_high)) = (trytes(a), trytes(b));
+                let (low, borrow) = a_low.carrying_sub(b_low, BalancedTrit::Zero);
+                let (high, borrow) = a_high.carrying_sub(b_high, borrow);
+
+                assert_eq!(a - b, low as i16 + high as i16 * 27 - borrow as i16 * 729);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fail_for_overflow() {
+        let _ = BalancedTryte::M + BalancedTryte::A;
+    }
+
     #[test]
     #[should_panic]
     fn fail_for_invalid_char() {